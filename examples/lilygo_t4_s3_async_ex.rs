@@ -0,0 +1,196 @@
+#![no_std]
+#![no_main]
+
+use rm690b0_rs::{
+    framebuffer_size, ColorMode, ControllerInterfaceAsync, DisplaySize, ResetDriver,
+    Rm690b0DriverAsync, DMA_CHUNK_SIZE,
+};
+
+use embedded_graphics::{
+    mono_font::{ascii::FONT_10X20, MonoTextStyle},
+    pixelcolor::Rgb888,
+    prelude::*,
+    text::{Alignment, Text, TextStyleBuilder},
+};
+
+extern crate alloc;
+use esp_alloc as _;
+use esp_backtrace as _;
+use esp_bootloader_esp_idf::esp_app_desc;
+use esp_hal::{
+    delay::Delay,
+    dma::DmaTxBuf,
+    dma_tx_buffer,
+    gpio::{Level, Output, OutputConfig},
+    main,
+    spi::{
+        master::{Address, Command, Config as SpiConfig, DataMode, Spi, SpiDma},
+        Error as SpiError, Mode,
+    },
+    time::Rate,
+    Async,
+};
+use esp_println::println;
+
+esp_app_desc!();
+
+const CMD_RAMWR: u32 = 0x2C;
+const CMD_RAMWRC: u32 = 0x3C;
+const QSPI_PIXEL_OPCODE: u16 = 0x32;
+const QSPI_CONTROL_OPCODE: u16 = 0x02;
+
+/// Async QSPI interface for the Lilygo T4-S3 built on `SpiDma` in async mode.
+struct Lgt4s3AsyncDriver {
+    spi: Option<SpiDma<'static, Async>>,
+    buffer: Option<DmaTxBuf>,
+}
+
+impl Lgt4s3AsyncDriver {
+    /// Writes one DMA transfer and awaits its completion.
+    async fn write(
+        &mut self,
+        data_mode: DataMode,
+        opcode: u16,
+        cmd: u32,
+        data: &[u8],
+    ) -> Result<(), SpiError> {
+        let spi = self.spi.take().unwrap();
+        let mut buffer = self.buffer.take().unwrap();
+        buffer.fill(data);
+
+        let result = spi.half_duplex_write(
+            data_mode,
+            Command::_8Bit(opcode, DataMode::Single),
+            Address::_24Bit(cmd << 8, DataMode::Single),
+            0,
+            data.len(),
+            buffer,
+        );
+        match result {
+            Ok(mut transfer) => {
+                transfer.wait_for_done().await;
+                let (spi, buffer) = transfer.wait();
+                self.spi = Some(spi);
+                self.buffer = Some(buffer);
+                Ok(())
+            }
+            Err((error, spi, buffer)) => {
+                self.spi = Some(spi);
+                self.buffer = Some(buffer);
+                Err(error)
+            }
+        }
+    }
+}
+
+impl ControllerInterfaceAsync for Lgt4s3AsyncDriver {
+    type Error = SpiError;
+
+    async fn send_command(&mut self, cmd: u8) -> Result<(), Self::Error> {
+        self.write(DataMode::Single, QSPI_CONTROL_OPCODE, cmd as u32, &[])
+            .await
+    }
+
+    async fn send_command_with_data(&mut self, cmd: u8, data: &[u8]) -> Result<(), Self::Error> {
+        self.write(DataMode::Single, QSPI_CONTROL_OPCODE, cmd as u32, data)
+            .await
+    }
+
+    async fn send_pixels(&mut self, pixels: &[u8]) -> Result<(), Self::Error> {
+        for (index, chunk) in pixels.chunks(DMA_CHUNK_SIZE).enumerate() {
+            let cmd = if index == 0 { CMD_RAMWR } else { CMD_RAMWRC };
+            self.write(DataMode::Quad, QSPI_PIXEL_OPCODE, cmd, chunk)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn send_pixels_continue(&mut self, pixels: &[u8]) -> Result<(), Self::Error> {
+        for chunk in pixels.chunks(DMA_CHUNK_SIZE) {
+            self.write(DataMode::Quad, QSPI_PIXEL_OPCODE, CMD_RAMWRC, chunk)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Adapts the blocking esp-hal delay to `embedded-hal-async`.
+struct AsyncDelay(Delay);
+
+impl embedded_hal_async::delay::DelayNs for AsyncDelay {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.0.delay_nanos(ns);
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+
+    esp_alloc::psram_allocator!(peripherals.PSRAM, esp_hal::psram);
+
+    // --- DMA Buffer for SPI ---
+    let dma_tx_buf = dma_tx_buffer!(DMA_CHUNK_SIZE).unwrap();
+
+    // --- SPI Configuration ---
+    let lcd_spi = Spi::new(
+        peripherals.SPI2,
+        SpiConfig::default()
+            .with_frequency(Rate::from_mhz(40_u32))
+            .with_mode(Mode::_0),
+    )
+    .unwrap()
+    .with_sio0(peripherals.GPIO14)
+    .with_sio1(peripherals.GPIO10)
+    .with_sio2(peripherals.GPIO16)
+    .with_sio3(peripherals.GPIO12)
+    .with_cs(peripherals.GPIO11)
+    .with_sck(peripherals.GPIO15)
+    .with_dma(peripherals.DMA_CH0)
+    .into_async();
+
+    // --- Pin Configuration ---
+    let output = Output::new(peripherals.GPIO13, Level::High, OutputConfig::default());
+    let _pwr_en = Output::new(peripherals.GPIO9, Level::High, OutputConfig::default());
+
+    let reset = ResetDriver::new(output, Delay::new());
+    let lg_driver = Lgt4s3AsyncDriver {
+        spi: Some(lcd_spi),
+        buffer: Some(dma_tx_buf),
+    };
+
+    // --- Display Setup ---
+    const DISPLAY_SIZE: DisplaySize = DisplaySize::new(450, 600);
+    const FB_SIZE: usize = framebuffer_size(DISPLAY_SIZE, ColorMode::Rgb888);
+
+    embassy_futures::block_on(async {
+        println!("Initializing RM690B0 Display...");
+        let mut display = Rm690b0DriverAsync::new_heap::<_, FB_SIZE>(
+            lg_driver,
+            reset,
+            ColorMode::Rgb888,
+            DISPLAY_SIZE,
+            AsyncDelay(Delay::new()),
+        )
+        .await
+        .expect("Display initialization failed");
+
+        let character_style = MonoTextStyle::new(&FONT_10X20, Rgb888::WHITE);
+        let text_style = TextStyleBuilder::new().alignment(Alignment::Center).build();
+        let text = "Hello, async LilyGo T4-S3!";
+
+        loop {
+            for col in (0..=DISPLAY_SIZE.width as i32).step_by(10) {
+                Text::with_text_style(text, Point::new(col, 300), character_style, text_style)
+                    .draw(&mut display)
+                    .unwrap();
+
+                if let Err(e) = display.flush().await {
+                    println!("Error flushing display: {:?}", e);
+                }
+
+                display.clear(Rgb888::BLACK).unwrap();
+            }
+        }
+    })
+}
@@ -7,6 +7,8 @@ use rm690b0_rs::{
     ColorMode,
     DisplaySize,
     Lgt4s3Driver,
+    PowerPin,
+    PoweredReset,
     ResetDriver,
     Rm690b0Driver,
     DMA_CHUNK_SIZE, // Note: Changed to Rm690b0Driver
@@ -77,9 +79,13 @@ fn main() -> ! {
 
     // --- Pin Configuration ---
     let output = Output::new(peripherals.GPIO13, Level::High, OutputConfig::default());
-    let mut pwr_en = Output::new(peripherals.GPIO9, Level::High, OutputConfig::default());
+    let pwr_en = Output::new(peripherals.GPIO9, Level::Low, OutputConfig::default());
 
-    let reset = ResetDriver::new(output);
+    // The driver switches the panel power on before releasing reset.
+    let reset = PoweredReset::new(
+        PowerPin::new(pwr_en),
+        ResetDriver::new(output, Delay::new()),
+    );
     let lg_driver = Lgt4s3Driver::new(lcd_spi);
 
     // --- Display Setup ---
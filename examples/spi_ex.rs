@@ -0,0 +1,92 @@
+#![no_std]
+#![no_main]
+
+//! Drives an RM690B0 module wired for 4-wire SPI (SCK, MOSI, CS, D/C) from an ESP32-S3.
+//! Adjust the pins to match your board.
+
+use rm690b0_rs::{
+    framebuffer_size, ColorMode, DisplaySize, ResetDriver, Rm690b0Driver, SpiInterface,
+};
+
+use embedded_graphics::{
+    pixelcolor::Rgb565,
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle},
+};
+use embedded_hal_bus::spi::ExclusiveDevice;
+
+extern crate alloc;
+use esp_alloc as _;
+use esp_backtrace as _;
+use esp_bootloader_esp_idf::esp_app_desc;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Level, Output, OutputConfig},
+    main,
+    spi::{
+        master::{Config as SpiConfig, Spi},
+        Mode,
+    },
+    time::Rate,
+};
+use esp_println::println;
+
+esp_app_desc!();
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+
+    esp_alloc::psram_allocator!(peripherals.PSRAM, esp_hal::psram);
+
+    // --- SPI Configuration ---
+    let spi_bus = Spi::new(
+        peripherals.SPI2,
+        SpiConfig::default()
+            .with_frequency(Rate::from_mhz(40_u32))
+            .with_mode(Mode::_0),
+    )
+    .unwrap()
+    .with_sck(peripherals.GPIO15)
+    .with_mosi(peripherals.GPIO14)
+    .with_miso(peripherals.GPIO10);
+
+    // --- Pin Configuration ---
+    let cs = Output::new(peripherals.GPIO11, Level::High, OutputConfig::default());
+    let dc = Output::new(peripherals.GPIO16, Level::Low, OutputConfig::default());
+    let rst = Output::new(peripherals.GPIO13, Level::High, OutputConfig::default());
+
+    let spi_device = ExclusiveDevice::new_no_delay(spi_bus, cs).unwrap();
+    let interface = SpiInterface::new(spi_device, dc);
+    let reset = ResetDriver::new(rst, Delay::new());
+
+    // --- Display Setup ---
+    const DISPLAY_SIZE: DisplaySize = DisplaySize::new(450, 600);
+    const FB_SIZE: usize = framebuffer_size(DISPLAY_SIZE, ColorMode::Rgb565);
+
+    println!("Initializing RM690B0 Display over SPI...");
+    let mut display = Rm690b0Driver::new_heap::<_, FB_SIZE>(
+        interface,
+        reset,
+        ColorMode::Rgb565,
+        DISPLAY_SIZE,
+        Delay::new(),
+    )
+    .expect("Display initialization failed");
+
+    let colors = [Rgb565::RED, Rgb565::GREEN, Rgb565::BLUE];
+
+    loop {
+        for color in colors {
+            display.clear(Rgb565::BLACK).unwrap();
+            Rectangle::new(Point::new(100, 200), Size::new(250, 200))
+                .into_styled(PrimitiveStyle::with_fill(color))
+                .draw(&mut display)
+                .unwrap();
+
+            if let Err(e) = display.flush() {
+                println!("Error flushing display: {:?}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,655 @@
+//! Async variant of the RM690B0 driver.
+//!
+//! Mirrors the blocking [`Rm690b0Driver`](crate::Rm690b0Driver) but performs
+//! bus transfers and delays through `async fn`s, so a long framebuffer
+//! transfer does not stall an async executor such as Embassy.
+
+use crate::builder::check_framebuffer_len;
+use crate::graphics_core::PixelEncoder;
+use crate::{
+    commands, partial_bounds, range_params, widen_to_even, window_params, ColorMode, DisplaySize,
+    DriverError, Endianness, Framebuffer, InitCommands, InitSequence, Orientation,
+    PixelFormatOptions, ResetInterface, Rm690b0Builder, TearMode, ALL_COLOR_MODES,
+};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use embedded_graphics_core::pixelcolor::Rgb888;
+use embedded_graphics_core::prelude::*;
+use embedded_graphics_core::primitives::Rectangle;
+use embedded_hal_async::delay::DelayNs;
+
+/// Async trait to implement the controller communication interface (QSPI, SPI, etc.).
+#[allow(async_fn_in_trait)]
+pub trait ControllerInterfaceAsync {
+    /// The specific error type for this interface implementation.
+    type Error;
+
+    /// Sends a command byte to the display.
+    async fn send_command(&mut self, cmd: u8) -> Result<(), Self::Error>;
+
+    /// Sends data bytes to the display following a command.
+    async fn send_command_with_data(&mut self, cmd: u8, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Sends pixel data
+    async fn send_pixels(&mut self, pixels: &[u8]) -> Result<(), Self::Error>;
+
+    /// Sends a batch of commands, each with its (possibly empty) data.
+    ///
+    /// Defaults to one [`Self::send_command`] or
+    /// [`Self::send_command_with_data`] per entry, like
+    /// [`ControllerInterface::send_sequence`](crate::ControllerInterface::send_sequence).
+    async fn send_sequence(&mut self, seq: &[(u8, &[u8])]) -> Result<(), Self::Error> {
+        for &(cmd, data) in seq {
+            if data.is_empty() {
+                self.send_command(cmd).await?;
+            } else {
+                self.send_command_with_data(cmd, data).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Starts a pixel write transaction for an already configured display window.
+    async fn send_pixels_start(&mut self, pixels: &[u8]) -> Result<(), Self::Error> {
+        self.send_pixels(pixels).await
+    }
+
+    /// Continues a pixel write transaction for an already configured display window.
+    async fn send_pixels_continue(&mut self, pixels: &[u8]) -> Result<(), Self::Error> {
+        self.send_pixels(pixels).await
+    }
+
+    /// Color modes the panel behind this interface is wired for.
+    ///
+    /// Defaults to every [`ColorMode`], like
+    /// [`ControllerInterface::supported_color_modes`](crate::ControllerInterface::supported_color_modes).
+    fn supported_color_modes(&self) -> &'static [ColorMode] {
+        ALL_COLOR_MODES
+    }
+}
+
+/// Result of building an async driver, carrying the interface and reset error types.
+type AsyncBuildResult<IFACE, RST, C> = Result<
+    Rm690b0DriverAsync<IFACE, RST, C>,
+    DriverError<<IFACE as ControllerInterfaceAsync>::Error, <RST as ResetInterface>::Error>,
+>;
+
+/// Async driver for the RM690B0 display controller.
+pub struct Rm690b0DriverAsync<IFACE, RST, C>
+where
+    IFACE: ControllerInterfaceAsync,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    pub(crate) interface: IFACE,
+    reset: RST,
+    framebuffer: Framebuffer,
+    config: DisplaySize,
+    color_mode: ColorMode,
+    orientation: Orientation,
+    /// Manufacturer-specific register writes sent during initialization.
+    init_sequence: InitSequence,
+    /// Tearing effect output mode set during initialization, `None` for TEOFF.
+    tear_mode: Option<TearMode>,
+    /// Inclusive panel row range last written to PTLAR.
+    partial_area: Option<(u16, u16)>,
+    /// Set while the panel is in partial display mode (PTLON), limiting what
+    /// `flush` transmits to `partial_area`.
+    partial_mode: bool,
+    /// Inclusive `(x_start, y_start, x_end, y_end)` bounds of pixels drawn since the last dirty flush.
+    dirty: Option<(u16, u16, u16, u16)>,
+    /// Whether drawing stores colors as B, G, R in the framebuffer.
+    bgr: bool,
+    /// Byte order drawing uses for RGB565 pixels.
+    rgb565_endianness: Endianness,
+    /// Last value written to WRDISBV, rewritten by `initialize_display`.
+    brightness: u8,
+    /// Options last set with `set_pixel_format_option`, kept in MADCTR across orientation changes.
+    pixel_format: PixelFormatOptions,
+    _color: core::marker::PhantomData<C>,
+}
+
+impl<IFACE, RST, C> Rm690b0Builder<IFACE, RST, C>
+where
+    IFACE: ControllerInterfaceAsync,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    /// Builds a [`Rm690b0DriverAsync`] with a boxed array framebuffer and initializes the display.
+    #[cfg(feature = "alloc")]
+    pub async fn build_async_heap<DELAY, const N: usize>(
+        self,
+        delay: DELAY,
+    ) -> AsyncBuildResult<IFACE, RST, C>
+    where
+        DELAY: DelayNs,
+    {
+        let config = self.validate_async(N)?;
+        self.build_async(config, Framebuffer::Heap(Box::new([0u8; N])), delay)
+            .await
+    }
+
+    /// Builds a [`Rm690b0DriverAsync`] with a static array framebuffer and initializes the display.
+    pub async fn build_async_static<DELAY, const N: usize>(
+        self,
+        delay: DELAY,
+        framebuffer: &'static mut [u8; N],
+    ) -> AsyncBuildResult<IFACE, RST, C>
+    where
+        DELAY: DelayNs,
+    {
+        let config = self.validate_async(N)?;
+        self.build_async(config, Framebuffer::Static(&mut framebuffer[..]), delay)
+            .await
+    }
+
+    /// Resolves the final display configuration and checks it against the framebuffer length.
+    fn validate_async(
+        &self,
+        len: usize,
+    ) -> Result<DisplaySize, DriverError<IFACE::Error, RST::Error>> {
+        let config = self
+            .resolve(self.interface.supported_color_modes())
+            .map_err(DriverError::InvalidConfiguration)?;
+        check_framebuffer_len(config, self.color_mode, len)
+            .map_err(DriverError::InvalidConfiguration)?;
+        Ok(config)
+    }
+
+    /// Creates the driver, then runs the reset and initialization sequence.
+    async fn build_async<DELAY>(
+        self,
+        config: DisplaySize,
+        framebuffer: Framebuffer,
+        mut delay: DELAY,
+    ) -> AsyncBuildResult<IFACE, RST, C>
+    where
+        DELAY: DelayNs,
+    {
+        let mut driver = Rm690b0DriverAsync {
+            interface: self.interface,
+            reset: self.reset,
+            framebuffer,
+            config,
+            color_mode: self.color_mode,
+            orientation: self.orientation,
+            init_sequence: self.init_sequence,
+            tear_mode: self.tear_mode,
+            partial_area: None,
+            partial_mode: false,
+            dirty: None,
+            bgr: false,
+            rgb565_endianness: Endianness::Big,
+            brightness: self.brightness,
+            pixel_format: PixelFormatOptions::default(),
+            _color: core::marker::PhantomData,
+        };
+        if !self.skip_reset {
+            driver.hard_reset()?;
+        }
+        driver.initialize_display(&mut delay).await?;
+        if self.invert_colors {
+            driver.send_command(commands::INVON).await?;
+        }
+        Ok(driver)
+    }
+}
+
+impl<IFACE, RST, C> Rm690b0DriverAsync<IFACE, RST, C>
+where
+    IFACE: ControllerInterfaceAsync,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    /// Creates a new driver instance with static array and initializes the display.
+    ///
+    /// Shorthand for [`Rm690b0Builder::build_async_static`] with only the color mode and size set.
+    pub async fn new_static<DELAY, const N: usize>(
+        interface: IFACE,
+        reset: RST,
+        colormode: ColorMode,
+        config: DisplaySize,
+        delay: DELAY,
+        framebuffer: &'static mut [u8; N],
+    ) -> Result<Self, DriverError<IFACE::Error, RST::Error>>
+    where
+        DELAY: DelayNs,
+    {
+        Rm690b0Builder::new(interface, reset)
+            .color_mode(colormode)
+            .size(config)
+            .build_async_static(delay, framebuffer)
+            .await
+    }
+
+    /// Creates a new driver instance with a boxed array framebuffer.
+    ///
+    /// Shorthand for [`Rm690b0Builder::build_async_heap`] with only the color mode and size set.
+    #[cfg(feature = "alloc")]
+    pub async fn new_heap<DELAY, const N: usize>(
+        interface: IFACE,
+        reset: RST,
+        color: ColorMode,
+        config: DisplaySize,
+        delay: DELAY,
+    ) -> Result<Self, DriverError<IFACE::Error, RST::Error>>
+    where
+        DELAY: DelayNs,
+    {
+        Rm690b0Builder::new(interface, reset)
+            .color_mode(color)
+            .size(config)
+            .build_async_heap::<_, N>(delay)
+            .await
+    }
+
+    /// Returns the color mode the driver was configured with.
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Swaps the red and blue channels when drawing into the framebuffer.
+    ///
+    /// Pixels already drawn are not converted. Gray8 is unaffected.
+    pub fn set_bgr(&mut self, bgr: bool) {
+        self.bgr = bgr;
+    }
+
+    /// Returns `true` if drawing swaps the red and blue channels.
+    pub fn bgr(&self) -> bool {
+        self.bgr
+    }
+
+    /// Sets the byte order in which drawing stores RGB565 pixels in the framebuffer.
+    ///
+    /// Defaults to [`Endianness::Big`]. Other color modes are unaffected, and
+    /// pixels already drawn are not converted.
+    pub fn set_rgb565_endianness(&mut self, endianness: Endianness) {
+        self.rgb565_endianness = endianness;
+    }
+
+    /// Returns the byte order used for RGB565 pixels in the framebuffer.
+    pub fn rgb565_endianness(&self) -> Endianness {
+        self.rgb565_endianness
+    }
+
+    /// Returns the logical `(width, height)` for the current orientation.
+    ///
+    /// This is also the layout of the framebuffer.
+    fn dimensions(&self) -> (u16, u16) {
+        self.config.logical_size(self.orientation)
+    }
+
+    /// Performs a hardware reset using the provided `ResetPin` implementation.
+    ///
+    /// The panel returns to normal display mode, so the partial area is forgotten.
+    pub fn hard_reset(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.reset.reset().map_err(DriverError::ResetError)?;
+        self.partial_area = None;
+        self.partial_mode = false;
+        Ok(())
+    }
+
+    /// Sends the essential initialization command sequence to the display.
+    ///
+    /// Sends the same commands as [`crate::Rm690b0Driver::initialize_display`].
+    pub async fn initialize_display<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>>
+    where
+        DELAY: DelayNs,
+    {
+        self.send_command(commands::SLPOUT).await?;
+        delay.delay_ms(120).await;
+
+        let init = InitCommands::new(
+            self.orientation_madctr(),
+            self.color_mode,
+            self.tear_mode,
+            self.brightness,
+        );
+        for seq in [&init.head()[..], self.init_sequence, &init.tail()[..]] {
+            self.interface
+                .send_sequence(seq)
+                .await
+                .map_err(DriverError::InterfaceError)?;
+        }
+        delay.delay_ms(20).await;
+
+        Ok(())
+    }
+
+    /// Send a command with no data
+    async fn send_command(&mut self, cmd: u8) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.interface
+            .send_command(cmd)
+            .await
+            .map_err(DriverError::InterfaceError)
+    }
+
+    /// Helper to send a command with associated data parameters
+    async fn send_command_with_data(
+        &mut self,
+        cmd: u8,
+        data: &[u8],
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.interface
+            .send_command_with_data(cmd, data)
+            .await
+            .map_err(DriverError::InterfaceError)
+    }
+
+    /// Turns the display panel off
+    pub async fn display_off(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command(commands::DISPOFF).await
+    }
+
+    /// Turns the display panel on
+    pub async fn display_on(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command(commands::DISPON).await
+    }
+
+    /// Sets the display brightness (0x00 - 0xFF for RM690B0).
+    pub async fn set_brightness(
+        &mut self,
+        value: u8,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command_with_data(commands::WRDISBV, &[value])
+            .await?;
+        self.brightness = value;
+        Ok(())
+    }
+
+    /// Sets the display orientation.
+    ///
+    /// Landscape orientations swap the logical width and height reported to
+    /// `embedded-graphics`. The framebuffer is laid out in logical
+    /// coordinates, so its contents should be redrawn after changing
+    /// orientation.
+    pub async fn set_orientation(
+        &mut self,
+        orientation: Orientation,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let madctr = orientation.madctr() | self.pixel_format.madctr_bits();
+        self.send_command_with_data(commands::MADCTR, &[madctr])
+            .await?;
+        self.orientation = orientation;
+        Ok(())
+    }
+
+    /// Returns the current display orientation.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Returns the MADCTR value for the current orientation and pixel format options.
+    fn orientation_madctr(&self) -> u8 {
+        self.orientation.madctr() | self.pixel_format.madctr_bits()
+    }
+
+    /// Sets the interface color order.
+    ///
+    /// Behaves like [`crate::Rm690b0Driver::set_pixel_format_option`]: the
+    /// color order is applied through the MADCTR RGB bit, keeping the current
+    /// orientation, and survives later orientation changes.
+    pub async fn set_pixel_format_option(
+        &mut self,
+        opts: PixelFormatOptions,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let madctr = self.orientation.madctr() | opts.madctr_bits();
+        self.send_command_with_data(commands::MADCTR, &[madctr])
+            .await?;
+        self.pixel_format = opts;
+        Ok(())
+    }
+
+    /// Returns the last options written with [`Self::set_pixel_format_option`].
+    pub fn pixel_format_option(&self) -> PixelFormatOptions {
+        self.pixel_format
+    }
+
+    /// Sets the partial display area (PTLAR) to panel rows `start_row..=end_row`.
+    ///
+    /// Once partial display mode is entered with [`Self::enable_partial_mode`],
+    /// [`Self::flush`] only transmits the active rows, as with
+    /// [`crate::Rm690b0Driver::set_partial_area`].
+    pub async fn set_partial_area(
+        &mut self,
+        start_row: u16,
+        end_row: u16,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if start_row > end_row || end_row >= self.config.height {
+            return Err(DriverError::InvalidConfiguration(
+                "Partial area rows must satisfy start_row <= end_row < height",
+            ));
+        }
+        self.send_command_with_data(commands::PTLAR, &range_params(start_row, end_row))
+            .await?;
+        self.partial_area = Some((start_row, end_row));
+        Ok(())
+    }
+
+    /// Returns the panel rows last set with [`Self::set_partial_area`].
+    pub fn partial_area(&self) -> Option<(u16, u16)> {
+        self.partial_area
+    }
+
+    /// Enters partial display mode (PTLON).
+    ///
+    /// Until [`Self::enable_normal_mode`], [`Self::flush`] only transmits the
+    /// rows set with [`Self::set_partial_area`].
+    pub async fn enable_partial_mode(
+        &mut self,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command(commands::PTLON).await?;
+        self.partial_mode = true;
+        Ok(())
+    }
+
+    /// Returns to normal display mode (NORON), lighting the whole panel again.
+    pub async fn enable_normal_mode(
+        &mut self,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command(commands::NORON).await?;
+        self.partial_mode = false;
+        Ok(())
+    }
+
+    /// Sets the active drawing window on the display RAM.
+    ///
+    /// Applies the same alignment rules and column/row offsets as
+    /// [`crate::Rm690b0Driver::set_window`].
+    pub async fn set_window(
+        &mut self,
+        x_start: u16,
+        y_start: u16,
+        x_end: u16,
+        y_end: u16,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let (caset, raset) = window_params(
+            (x_start, y_start, x_end, y_end),
+            self.dimensions(),
+            self.config.ram_offset(self.orientation),
+            self.orientation.is_landscape(),
+        )
+        .map_err(DriverError::InvalidConfiguration)?;
+        self.send_command_with_data(commands::CASET, &caset).await?;
+        self.send_command_with_data(commands::RASET, &raset).await
+    }
+
+    /// Writes the contents of the framebuffer to the display RAM.
+    ///
+    /// In partial display mode only the rows of the partial area are sent.
+    pub async fn flush(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if let Some((start, end)) = self.partial_area.filter(|_| self.partial_mode) {
+            let (x_start, y_start, x_end, y_end) =
+                partial_bounds((start, end), self.orientation, self.dimensions());
+            return self.flush_bounds(x_start, y_start, x_end, y_end).await;
+        }
+
+        // Set window to full display
+        let (width, height) = self.dimensions();
+        self.set_window(0, 0, width - 1, height - 1).await?;
+        self.interface
+            .send_pixels(&self.framebuffer)
+            .await
+            .map_err(DriverError::InterfaceError)?;
+        Ok(())
+    }
+
+    /// Flushes a rectangular region from the framebuffer to display RAM.
+    ///
+    /// `area` must lie within the display and satisfy the alignment
+    /// constraints enforced by [`Self::set_window`].
+    pub async fn partial_flush_area(
+        &mut self,
+        area: &Rectangle,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let Some(bottom_right) = area.bottom_right() else {
+            return Err(DriverError::InvalidConfiguration("Flush area is empty"));
+        };
+        let (width, height) = self.dimensions();
+        if area.top_left.x < 0
+            || area.top_left.y < 0
+            || bottom_right.x >= width as i32
+            || bottom_right.y >= height as i32
+        {
+            return Err(DriverError::InvalidConfiguration(
+                "Flush area lies outside the display",
+            ));
+        }
+        self.flush_bounds(
+            area.top_left.x as u16,
+            area.top_left.y as u16,
+            bottom_right.x as u16,
+            bottom_right.y as u16,
+        )
+        .await
+    }
+
+    /// Returns the bounding box of everything drawn since the last dirty flush.
+    pub fn dirty_region(&self) -> Option<Rectangle> {
+        self.dirty.map(|(x_start, y_start, x_end, y_end)| {
+            Rectangle::with_corners(
+                Point::new(x_start as i32, y_start as i32),
+                Point::new(x_end as i32, y_end as i32),
+            )
+        })
+    }
+
+    /// Forgets all tracked changes without flushing them.
+    pub fn clear_dirty(&mut self) {
+        self.dirty = None;
+    }
+
+    /// Flushes only the region drawn since the last dirty flush, then resets it.
+    ///
+    /// The region is widened to even boundaries to satisfy the window
+    /// alignment rules. Does nothing if nothing was drawn.
+    pub async fn flush_dirty(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let Some(bounds) = self.dirty else {
+            return Ok(());
+        };
+        let (x_start, y_start, x_end, y_end) = widen_to_even(bounds, self.dimensions());
+        self.flush_bounds(x_start, y_start, x_end, y_end).await?;
+        self.dirty = None;
+        Ok(())
+    }
+
+    /// Sends the framebuffer rows within the inclusive bounds, in [`Self::set_window`] order.
+    async fn flush_bounds(
+        &mut self,
+        x_start: u16,
+        y_start: u16,
+        x_end: u16,
+        y_end: u16,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if x_end < x_start || y_end < y_start {
+            return Err(DriverError::InvalidConfiguration(
+                "Flush region end must not be before its start",
+            ));
+        }
+        self.set_window(x_start, y_start, x_end, y_end).await?;
+        let bytes_per_pixel = self.color_mode.bytes_per_pixel();
+        let fb_width = self.dimensions().0 as usize * bytes_per_pixel;
+        let width = (x_end - x_start + 1) as usize;
+        let height = (y_end - y_start + 1) as usize;
+        for y in 0..height {
+            let offset = (y_start as usize + y) * fb_width + (x_start as usize * bytes_per_pixel);
+            let row_end = offset + (width * bytes_per_pixel);
+            if offset >= self.framebuffer.len() || row_end > self.framebuffer.len() {
+                return Err(DriverError::InvalidConfiguration(
+                    "Framebuffer slice out of bounds",
+                ));
+            }
+
+            let row = &self.framebuffer[offset..row_end];
+            if y == 0 {
+                self.interface
+                    .send_pixels_start(row)
+                    .await
+                    .map_err(DriverError::InterfaceError)?;
+            } else {
+                self.interface
+                    .send_pixels_continue(row)
+                    .await
+                    .map_err(DriverError::InterfaceError)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<IFACE, RST, C> DrawTarget for Rm690b0DriverAsync<IFACE, RST, C>
+where
+    IFACE: ControllerInterfaceAsync,
+    RST: ResetInterface,
+    C: PixelColor + Into<Rgb888>,
+{
+    type Color = C;
+    // Drawing to the framebuffer in memory is infallible.
+    // Errors happen during flush with SPI comms.
+    type Error = core::convert::Infallible;
+
+    /// Draws a single pixel to the internal framebuffer.
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bytes_per_pixel = self.color_mode.bytes_per_pixel();
+        let encoder = PixelEncoder::new(self.color_mode, self.bgr, self.rgb565_endianness);
+        let (width, height) = self.dimensions();
+        let mut dirty = self.dirty;
+
+        for Pixel(coord, color) in pixels.into_iter() {
+            if coord.x >= 0 && coord.x < width as i32 && coord.y >= 0 && coord.y < height as i32 {
+                let (x, y) = (coord.x as u16, coord.y as u16);
+                let index = (y as usize * width as usize + x as usize) * bytes_per_pixel;
+                let pixel_end = index + bytes_per_pixel;
+
+                if pixel_end <= self.framebuffer.len() {
+                    encoder.encode(color.into(), &mut self.framebuffer[index..pixel_end]);
+                    dirty = Some(match dirty {
+                        Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+                        None => (x, y, x, y),
+                    });
+                }
+            }
+        }
+        self.dirty = dirty;
+        Ok(())
+    }
+}
+
+impl<IFACE, RST, C> OriginDimensions for Rm690b0DriverAsync<IFACE, RST, C>
+where
+    IFACE: ControllerInterfaceAsync,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    fn size(&self) -> Size {
+        let (width, height) = self.dimensions();
+        Size::new(width as u32, height as u32)
+    }
+}
@@ -0,0 +1,287 @@
+//! Builder for configuring and initializing an [`Rm690b0Driver`].
+
+use crate::{
+    checked_framebuffer_size, dirty_rows_for, ColorMode, ControllerInterface, DisplaySize,
+    DriverError, Endianness, Framebuffer, InitSequence, Orientation, PanelState,
+    PixelFormatOptions, ResetInterface, Rm690b0Driver, SoftwareRotation, TearMode, WireFormat,
+    LILYGO_T4_S3_INIT,
+};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use embedded_graphics::prelude::PixelColor;
+use embedded_hal::delay::DelayNs;
+
+/// Checks that a framebuffer of `len` bytes matches the display size and color mode.
+pub(crate) fn check_framebuffer_len(
+    config: DisplaySize,
+    color_mode: ColorMode,
+    len: usize,
+) -> Result<(), &'static str> {
+    match checked_framebuffer_size(config, color_mode) {
+        Some(size) if size == len => Ok(()),
+        Some(_) => Err("Framebuffer size does not match display size and color mode"),
+        None => Err("Framebuffer size overflows usize"),
+    }
+}
+
+/// Result of building a driver, carrying the interface and reset error types.
+type BuildResult<IFACE, RST, C> = Result<
+    Rm690b0Driver<IFACE, RST, C>,
+    DriverError<<IFACE as ControllerInterface>::Error, <RST as ResetInterface>::Error>,
+>;
+
+/// Builder for [`Rm690b0Driver`].
+///
+/// Collects the display configuration, then allocates the framebuffer,
+/// performs the hardware reset and runs the initialization sequence in
+/// [`Self::build_heap`] or [`Self::build_static`]. The display size is
+/// required; everything else has a default. The same configuration builds
+/// the framebuffer-less [`Rm690b0DirectDriver`](crate::Rm690b0DirectDriver)
+/// with `build_direct`, and the async driver with `build_async_heap` or
+/// `build_async_static` when the `async` feature is enabled.
+pub struct Rm690b0Builder<IFACE, RST, C>
+where
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    pub(crate) interface: IFACE,
+    pub(crate) reset: RST,
+    pub(crate) color_mode: ColorMode,
+    size: Option<DisplaySize>,
+    pub(crate) orientation: Orientation,
+    pub(crate) brightness: u8,
+    col_offset: Option<u16>,
+    row_offset: Option<u16>,
+    pub(crate) invert_colors: bool,
+    pub(crate) init_sequence: InitSequence,
+    pub(crate) tear_mode: Option<TearMode>,
+    pub(crate) skip_reset: bool,
+    _color: core::marker::PhantomData<C>,
+}
+
+impl<IFACE, RST, C> Rm690b0Builder<IFACE, RST, C>
+where
+    RST: ResetInterface,
+    C: WireFormat,
+{
+    /// Creates a builder whose color mode is the native wire format of `C`.
+    ///
+    /// Equivalent to [`Self::new`] followed by
+    /// `color_mode(C::COLOR_MODE)`; size the framebuffer with
+    /// [`framebuffer_size_of`](crate::framebuffer_size_of).
+    pub fn native(interface: IFACE, reset: RST) -> Self {
+        Self::new(interface, reset).color_mode(C::COLOR_MODE)
+    }
+}
+
+impl<IFACE, RST, C> Rm690b0Builder<IFACE, RST, C>
+where
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    /// Creates a builder for the given controller interface and reset pin.
+    ///
+    /// Defaults to RGB888, portrait orientation, full brightness, no color
+    /// inversion, a V-blank tearing effect output and the Lilygo T4-S3
+    /// initialization sequence.
+    pub fn new(interface: IFACE, reset: RST) -> Self {
+        Rm690b0Builder {
+            interface,
+            reset,
+            color_mode: ColorMode::Rgb888,
+            size: None,
+            orientation: Orientation::Portrait,
+            brightness: 0xFF,
+            col_offset: None,
+            row_offset: None,
+            invert_colors: false,
+            init_sequence: LILYGO_T4_S3_INIT,
+            tear_mode: Some(TearMode::VBlankOnly),
+            skip_reset: false,
+            _color: core::marker::PhantomData,
+        }
+    }
+
+    /// Sets the interface pixel format.
+    pub fn color_mode(mut self, color_mode: ColorMode) -> Self {
+        self.color_mode = color_mode;
+        self
+    }
+
+    /// Sets the display size. Required.
+    pub fn size(mut self, size: DisplaySize) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Sets the initial display orientation.
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Sets the brightness written during initialization, before DISPON.
+    pub fn brightness(mut self, brightness: u8) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    /// Sets the first display RAM column of the visible area, overriding the size's offset.
+    pub fn col_offset(mut self, col_offset: u16) -> Self {
+        self.col_offset = Some(col_offset);
+        self
+    }
+
+    /// Sets the first display RAM row of the visible area, overriding the size's offset.
+    pub fn row_offset(mut self, row_offset: u16) -> Self {
+        self.row_offset = Some(row_offset);
+        self
+    }
+
+    /// Enables display color inversion (INVON) after initialization.
+    pub fn invert_colors(mut self, invert: bool) -> Self {
+        self.invert_colors = invert;
+        self
+    }
+
+    /// Sets the manufacturer-specific `(command, data)` writes sent during initialization.
+    ///
+    /// They are applied after SLPOUT and MADCTR and before COLMOD. Defaults
+    /// to [`LILYGO_T4_S3_INIT`]; other RM690B0 modules usually need their
+    /// vendor's values.
+    pub fn init_sequence(mut self, sequence: InitSequence) -> Self {
+        self.init_sequence = sequence;
+        self
+    }
+
+    /// Sets the tearing effect output mode, or `None` to send TEOFF for an unwired TE pin.
+    pub fn tear_mode(mut self, mode: Option<TearMode>) -> Self {
+        self.tear_mode = mode;
+        self
+    }
+
+    /// Skips the hardware reset before initialization.
+    ///
+    /// For boards where the reset line is shared and already pulsed by
+    /// board-level init code, avoiding a second reset and the flash it
+    /// causes. The caller must guarantee the panel has just been reset and
+    /// is in its power-on state, otherwise initialization may misbehave.
+    pub fn skip_reset(mut self, skip: bool) -> Self {
+        self.skip_reset = skip;
+        self
+    }
+
+    /// Resolves the final display configuration and checks the color mode
+    /// against the modes the interface supports.
+    pub(crate) fn resolve(&self, supported: &[ColorMode]) -> Result<DisplaySize, &'static str> {
+        let Some(mut config) = self.size else {
+            return Err("Display size is required");
+        };
+        config.col_offset = self.col_offset.unwrap_or(config.col_offset);
+        config.row_offset = self.row_offset.unwrap_or(config.row_offset);
+
+        if !supported.contains(&self.color_mode) {
+            return Err("unsupported color mode for this panel");
+        }
+        Ok(config)
+    }
+}
+
+impl<IFACE, RST, C> Rm690b0Builder<IFACE, RST, C>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    /// Builds the driver with a boxed array framebuffer and initializes the display.
+    #[cfg(feature = "alloc")]
+    pub fn build_heap<DELAY, const N: usize>(self, delay: DELAY) -> BuildResult<IFACE, RST, C>
+    where
+        DELAY: DelayNs,
+    {
+        let config = self.validate(N)?;
+        self.build(config, Framebuffer::Heap(Box::new([0u8; N])), delay)
+    }
+
+    /// Builds the driver with a static array framebuffer and initializes the display.
+    pub fn build_static<DELAY, const N: usize>(
+        self,
+        delay: DELAY,
+        framebuffer: &'static mut [u8; N],
+    ) -> BuildResult<IFACE, RST, C>
+    where
+        DELAY: DelayNs,
+    {
+        let config = self.validate(N)?;
+        self.build(config, Framebuffer::Static(&mut framebuffer[..]), delay)
+    }
+
+    /// Resolves the final display configuration and checks it against the framebuffer length.
+    fn validate(&self, len: usize) -> Result<DisplaySize, DriverError<IFACE::Error, RST::Error>> {
+        let config = self
+            .resolve(self.interface.supported_color_modes())
+            .map_err(DriverError::InvalidConfiguration)?;
+        check_framebuffer_len(config, self.color_mode, len)
+            .map_err(DriverError::InvalidConfiguration)?;
+        Ok(config)
+    }
+
+    /// Creates the driver, then runs the reset and initialization sequence.
+    fn build<DELAY>(
+        self,
+        config: DisplaySize,
+        framebuffer: Framebuffer,
+        mut delay: DELAY,
+    ) -> BuildResult<IFACE, RST, C>
+    where
+        DELAY: DelayNs,
+    {
+        let Some(dirty_rows) = dirty_rows_for(config) else {
+            return Err(DriverError::InvalidConfiguration(
+                "Display has more rows than the dirty row bitmap can track",
+            ));
+        };
+        let mut driver = Rm690b0Driver {
+            interface: self.interface,
+            reset: self.reset,
+            framebuffer,
+            config,
+            color_mode: self.color_mode,
+            init_sequence: self.init_sequence,
+            tear_mode: self.tear_mode,
+            min_frame_interval_us: 0,
+            last_flush_us: None,
+            partial_area: None,
+            partial_mode: false,
+            madctr: 0x00,
+            pixel_shift: (0, 0),
+            burn_in_step: 0,
+            orientation: self.orientation,
+            software_rotation: SoftwareRotation::None,
+            pixel_format: PixelFormatOptions::default(),
+            bgr: false,
+            rgb565_endianness: Endianness::Big,
+            inverted: false,
+            panel_state: PanelState::RESET,
+            all_pixels_forced: false,
+            back_buffer: None,
+            dirty: None,
+            dirty_rows,
+            brightness: self.brightness,
+            rad_acl: 0x00,
+            frame_rate_level: None,
+            resume_row: None,
+            #[cfg(any(test, feature = "test-internals"))]
+            window: None,
+            _color: core::marker::PhantomData,
+        };
+        if !self.skip_reset {
+            driver.hard_reset()?;
+        }
+        driver.initialize_display(&mut delay)?;
+        if self.invert_colors {
+            driver.set_inversion(true)?;
+        }
+        Ok(driver)
+    }
+}
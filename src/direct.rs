@@ -0,0 +1,395 @@
+//! Framebuffer-less variant of the RM690B0 driver.
+//!
+//! [`Rm690b0DirectDriver`] implements `DrawTarget` by writing every drawing
+//! operation straight to display RAM, trading bus traffic for the memory a
+//! framebuffer needs (about 860KB for a 480x600 RGB888 panel).
+//!
+//! The controller only accepts windows with an even start and an even size,
+//! and display RAM cannot be read back, so regions are widened to even
+//! boundaries. The extra column or row repeats the nearest edge pixel, and a
+//! lone pixel from `draw_iter` fills the 2x2 cell that contains it. Shapes
+//! drawn through `fill_solid`/`fill_contiguous` on even boundaries are exact.
+
+use crate::graphics_core::PixelEncoder;
+use crate::{
+    commands, window_params, ColorMode, ControllerInterface, DisplaySize, DriverError, Endianness,
+    InitCommands, InitSequence, Orientation, ResetInterface, Rm690b0Builder, TearMode,
+    ROW_BUFFER_LEN,
+};
+use embedded_graphics_core::pixelcolor::Rgb888;
+use embedded_graphics_core::prelude::*;
+use embedded_graphics_core::primitives::Rectangle;
+use embedded_hal::delay::DelayNs;
+
+/// Result of building a direct driver, carrying the interface and reset error types.
+type DirectBuildResult<IFACE, RST, C> = Result<
+    Rm690b0DirectDriver<IFACE, RST, C>,
+    DriverError<<IFACE as ControllerInterface>::Error, <RST as ResetInterface>::Error>,
+>;
+
+/// Driver for the RM690B0 that draws directly to display RAM without a framebuffer.
+pub struct Rm690b0DirectDriver<IFACE, RST, C>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    pub(crate) interface: IFACE,
+    reset: RST,
+    config: DisplaySize,
+    color_mode: ColorMode,
+    orientation: Orientation,
+    /// Manufacturer-specific register writes sent during initialization.
+    init_sequence: InitSequence,
+    /// Tearing effect output mode set during initialization, `None` for TEOFF.
+    tear_mode: Option<TearMode>,
+    /// Whether drawing sends colors as B, G, R.
+    bgr: bool,
+    /// Byte order drawing uses for RGB565 pixels.
+    rgb565_endianness: Endianness,
+    /// Last value written to WRDISBV, rewritten by `initialize_display`.
+    brightness: u8,
+    _color: core::marker::PhantomData<C>,
+}
+
+impl<IFACE, RST, C> Rm690b0Builder<IFACE, RST, C>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    /// Builds a [`Rm690b0DirectDriver`], which needs no framebuffer, and initializes the display.
+    pub fn build_direct<DELAY>(self, mut delay: DELAY) -> DirectBuildResult<IFACE, RST, C>
+    where
+        DELAY: DelayNs,
+    {
+        let config = self
+            .resolve(self.interface.supported_color_modes())
+            .map_err(DriverError::InvalidConfiguration)?;
+        let mut driver = Rm690b0DirectDriver {
+            interface: self.interface,
+            reset: self.reset,
+            config,
+            color_mode: self.color_mode,
+            orientation: self.orientation,
+            init_sequence: self.init_sequence,
+            tear_mode: self.tear_mode,
+            bgr: false,
+            rgb565_endianness: Endianness::Big,
+            brightness: self.brightness,
+            _color: core::marker::PhantomData,
+        };
+        if !self.skip_reset {
+            driver.hard_reset()?;
+        }
+        driver.initialize_display(&mut delay)?;
+        if self.invert_colors {
+            driver.send_command(commands::INVON)?;
+        }
+        Ok(driver)
+    }
+}
+
+impl<IFACE, RST, C> Rm690b0DirectDriver<IFACE, RST, C>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    /// Creates a new driver instance and initializes the display.
+    ///
+    /// Shorthand for [`Rm690b0Builder::build_direct`] with only the color mode and size set.
+    pub fn new<DELAY>(
+        interface: IFACE,
+        reset: RST,
+        color_mode: ColorMode,
+        config: DisplaySize,
+        delay: DELAY,
+    ) -> Result<Self, DriverError<IFACE::Error, RST::Error>>
+    where
+        DELAY: DelayNs,
+    {
+        Rm690b0Builder::new(interface, reset)
+            .color_mode(color_mode)
+            .size(config)
+            .build_direct(delay)
+    }
+
+    /// Returns the color mode the driver was configured with.
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Returns the display orientation set when building the driver.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Swaps the red and blue channels of every color drawn from now on.
+    ///
+    /// Gray8 is unaffected.
+    pub fn set_bgr(&mut self, bgr: bool) {
+        self.bgr = bgr;
+    }
+
+    /// Returns `true` if drawing swaps the red and blue channels.
+    pub fn bgr(&self) -> bool {
+        self.bgr
+    }
+
+    /// Sets the byte order in which drawing sends RGB565 pixels.
+    ///
+    /// Defaults to [`Endianness::Big`]. Other color modes are unaffected.
+    pub fn set_rgb565_endianness(&mut self, endianness: Endianness) {
+        self.rgb565_endianness = endianness;
+    }
+
+    /// Returns the byte order used for RGB565 pixels.
+    pub fn rgb565_endianness(&self) -> Endianness {
+        self.rgb565_endianness
+    }
+
+    /// Returns the encoder drawing uses for the color mode and layout options.
+    fn pixel_encoder(&self) -> PixelEncoder {
+        PixelEncoder::new(self.color_mode, self.bgr, self.rgb565_endianness)
+    }
+
+    /// Performs a hardware reset using the provided `ResetPin` implementation.
+    pub fn hard_reset(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.reset.reset().map_err(DriverError::ResetError)?;
+        Ok(())
+    }
+
+    /// Sends the essential initialization command sequence to the display.
+    ///
+    /// Sends the same commands as [`crate::Rm690b0Driver::initialize_display`].
+    pub fn initialize_display<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>>
+    where
+        DELAY: DelayNs,
+    {
+        self.send_command(commands::SLPOUT)?;
+        delay.delay_ms(120);
+
+        InitCommands::new(
+            self.orientation.madctr(),
+            self.color_mode,
+            self.tear_mode,
+            self.brightness,
+        )
+        .send(&mut self.interface, self.init_sequence)
+        .map_err(DriverError::InterfaceError)?;
+        delay.delay_ms(20);
+        Ok(())
+    }
+
+    /// Send a command with no data
+    fn send_command(&mut self, cmd: u8) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.interface
+            .send_command(cmd)
+            .map_err(DriverError::InterfaceError)
+    }
+
+    /// Helper to send a command with associated data parameters
+    fn send_command_with_data(
+        &mut self,
+        cmd: u8,
+        data: &[u8],
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.interface
+            .send_command_with_data(cmd, data)
+            .map_err(DriverError::InterfaceError)
+    }
+
+    /// Turns the display panel off
+    pub fn display_off(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command(commands::DISPOFF)
+    }
+
+    /// Turns the display panel on
+    pub fn display_on(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command(commands::DISPON)
+    }
+
+    /// Sets the display brightness (0x00 - 0xFF for RM690B0).
+    pub fn set_brightness(
+        &mut self,
+        value: u8,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command_with_data(commands::WRDISBV, &[value])?;
+        self.brightness = value;
+        Ok(())
+    }
+
+    /// Sets the active drawing window on the display RAM.
+    ///
+    /// Applies the same alignment rules and column/row offsets as
+    /// [`crate::Rm690b0Driver::set_window`].
+    pub fn set_window(
+        &mut self,
+        x_start: u16,
+        y_start: u16,
+        x_end: u16,
+        y_end: u16,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let (caset, raset) = window_params(
+            (x_start, y_start, x_end, y_end),
+            self.config.logical_size(self.orientation),
+            self.config.ram_offset(self.orientation),
+            self.orientation.is_landscape(),
+        )
+        .map_err(DriverError::InvalidConfiguration)?;
+        self.send_command_with_data(commands::CASET, &caset)?;
+        self.send_command_with_data(commands::RASET, &raset)
+    }
+
+    /// Writes encoded rows to an even-aligned window covering `area`.
+    ///
+    /// `area` must lie within the display. It is widened to even bounds;
+    /// `row` is called once per row of `area`, in order, to fill the row's
+    /// encoded pixels, and the padding column and rows repeat the nearest
+    /// edge pixels.
+    fn write_region(
+        &mut self,
+        area: &Rectangle,
+        mut row: impl FnMut(&mut [u8]),
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+        let bytes_per_pixel = self.color_mode.bytes_per_pixel();
+        let (x_start, y_start) = (area.top_left.x as u16, area.top_left.y as u16);
+        let (x_end, y_end) = (bottom_right.x as u16, bottom_right.y as u16);
+        let (window_x_start, window_y_start) = (x_start & !1, y_start & !1);
+        let (window_x_end, window_y_end) = (x_end | 1, y_end | 1);
+        self.set_window(window_x_start, window_y_start, window_x_end, window_y_end)?;
+
+        let pad_left = (x_start - window_x_start) as usize * bytes_per_pixel;
+        let width = area.size.width as usize * bytes_per_pixel;
+        let mut buffer = [0u8; ROW_BUFFER_LEN];
+        let Some(buffer) =
+            buffer.get_mut(..(window_x_end - window_x_start + 1) as usize * bytes_per_pixel)
+        else {
+            return Err(DriverError::InvalidConfiguration(
+                "Window is wider than the row buffer",
+            ));
+        };
+
+        let mut first = true;
+        for y in y_start..=y_end {
+            row(&mut buffer[pad_left..pad_left + width]);
+            if pad_left > 0 {
+                buffer.copy_within(pad_left..pad_left + bytes_per_pixel, 0);
+            }
+            let last = pad_left + width;
+            if last < buffer.len() {
+                buffer.copy_within(last - bytes_per_pixel..last, last);
+            }
+
+            // An odd first row is preceded by a copy of itself, and an even
+            // last row is followed by one.
+            let mut repeats = 1;
+            if y == y_start && y_start != window_y_start {
+                repeats += 1;
+            }
+            if y == y_end && y_end != window_y_end {
+                repeats += 1;
+            }
+            for _ in 0..repeats {
+                let result = if first {
+                    self.interface.send_pixels_start(buffer)
+                } else {
+                    self.interface.send_pixels_continue(buffer)
+                };
+                result.map_err(DriverError::InterfaceError)?;
+                first = false;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<IFACE, RST, C> DrawTarget for Rm690b0DirectDriver<IFACE, RST, C>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor + Into<Rgb888>,
+{
+    type Color = C;
+    // Every drawing operation is a bus transfer.
+    type Error = DriverError<IFACE::Error, RST::Error>;
+
+    /// Writes each pixel as the 2x2 cell that contains it.
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounds = self.bounding_box();
+        for Pixel(coord, color) in pixels.into_iter() {
+            if bounds.contains(coord) {
+                self.fill_solid(&Rectangle::new(coord, Size::new(1, 1)), color)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a row-major stream of colors to the visible part of `area`.
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let clipped = area.intersection(&self.bounding_box());
+        if clipped.is_zero_sized() {
+            return Ok(());
+        }
+
+        let bytes_per_pixel = self.color_mode.bytes_per_pixel();
+        let encoder = self.pixel_encoder();
+        let area_width = area.size.width as usize;
+        let skip_left = (clipped.top_left.x - area.top_left.x) as usize;
+        let skip_right = area_width - skip_left - clipped.size.width as usize;
+        let rows_above = (clipped.top_left.y - area.top_left.y) as usize;
+
+        let mut colors = colors.into_iter();
+        colors.by_ref().take(rows_above * area_width).for_each(drop);
+
+        self.write_region(&clipped, |out| {
+            colors.by_ref().take(skip_left).for_each(drop);
+            for pixel in out.chunks_exact_mut(bytes_per_pixel) {
+                if let Some(color) = colors.next() {
+                    encoder.encode(color.into(), pixel);
+                }
+            }
+            colors.by_ref().take(skip_right).for_each(drop);
+        })
+    }
+
+    /// Encodes the color once and writes it to the visible part of `area`.
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let bytes_per_pixel = self.color_mode.bytes_per_pixel();
+        let mut encoded = [0u8; 3];
+        let encoded = &mut encoded[..bytes_per_pixel];
+        self.pixel_encoder().encode(color.into(), encoded);
+
+        self.write_region(&area, |out| {
+            for pixel in out.chunks_exact_mut(bytes_per_pixel) {
+                pixel.copy_from_slice(encoded);
+            }
+        })
+    }
+}
+
+impl<IFACE, RST, C> OriginDimensions for Rm690b0DirectDriver<IFACE, RST, C>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    fn size(&self) -> Size {
+        let (width, height) = self.config.logical_size(self.orientation);
+        Size::new(width as u32, height as u32)
+    }
+}
@@ -1,11 +1,12 @@
 //! Driver implementation for Lilygo T4-S3
 //! Uses QSPI interface and I2C-based GPIO expander or GPIO for reset.
 
-use crate::{ControllerInterface, ResetInterface};
+use crate::{ControllerInterface, ControllerReadInterface};
+use core::num::NonZeroUsize;
 use esp_hal::{
-    delay::Delay,
+    dma::{DmaRxBuf, DmaTxBuf},
     spi::{
-        master::{Address, Command, DataMode, SpiDmaBus},
+        master::{Address, Command, DataMode, SpiDmaBus, SpiDmaTransfer},
         Error as SpiError,
     },
     Blocking,
@@ -13,19 +14,157 @@ use esp_hal::{
 
 const CMD_RAMWR: u32 = 0x2C;
 const CMD_RAMWRC: u32 = 0x3C;
-const QSPI_PIXEL_OPCODE: u8 = 0x32;
-const QSPI_CONTROL_OPCODE: u8 = 0x02;
+/// Default QSPI opcode for pixel writes.
+pub const QSPI_PIXEL_OPCODE: u8 = 0x32;
+/// Default QSPI opcode for command writes.
+pub const QSPI_CONTROL_OPCODE: u8 = 0x02;
+const QSPI_READ_OPCODE: u8 = 0x03;
+/// Default maximum number of bytes sent per QSPI DMA transfer.
 pub const DMA_CHUNK_SIZE: usize = 16380;
 
-/// QSPI implementation of ControllerInterface for SH8601
+/// Width of the QSPI command (opcode) phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandWidth {
+    /// 8-bit opcode (Lilygo T4-S3 and most RM690B0 QSPI modules).
+    Bits8,
+    /// 16-bit opcode, for modules whose bridge expects a padded opcode phase.
+    Bits16,
+}
+
+/// Width of the QSPI address phase.
+///
+/// The DCS command is always placed in bits 15:8 of the address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressWidth {
+    /// 16-bit address phase.
+    Bits16,
+    /// 24-bit address phase (Lilygo T4-S3 and most RM690B0 QSPI modules).
+    Bits24,
+    /// 32-bit address phase.
+    Bits32,
+}
+
+/// QSPI implementation of ControllerInterface for RM690B0
+///
+/// Defaults to an 8-bit command and 24-bit address phase, which is what the
+/// Lilygo T4-S3 expects. Modules that frame the command differently can
+/// override this with [`Lgt4s3Driver::with_command_width`] and
+/// [`Lgt4s3Driver::with_address_width`]. Pixel data is split into
+/// transfers of [`DMA_CHUNK_SIZE`] bytes unless changed with
+/// [`Lgt4s3Driver::with_chunk_size`]. Sibling panels with different QSPI
+/// opcodes can be driven through [`Lgt4s3Driver::with_opcodes`].
+///
+/// [`ControllerInterface::send_pixels_deferred`] sends all but the last
+/// chunk of the frame, then copies the last chunk into the bus's DMA buffer
+/// and returns while it is still being transferred, so a double-buffered
+/// flush overlaps drawing with up to one chunk. Any later use of the bus
+/// waits for that transfer to finish first.
 pub struct Lgt4s3Driver {
-    pub qspi: SpiDmaBus<'static, Blocking>,
+    /// Always `Some` between calls; taken while switching between states.
+    bus: Option<Bus>,
+    command_width: CommandWidth,
+    address_width: AddressWidth,
+    chunk_size: usize,
+    control_opcode: u8,
+    pixel_opcode: u8,
+}
+
+/// State of the QSPI bus around deferred pixel transfers.
+enum Bus {
+    /// No transfer is running.
+    Idle(SpiDmaBus<'static, Blocking>),
+    /// A deferred pixel transfer is running; the receive buffer is kept to
+    /// rebuild the bus once it finishes.
+    Busy(SpiDmaTransfer<'static, Blocking, DmaTxBuf>, DmaRxBuf),
 }
 
 impl Lgt4s3Driver {
     /// Creates a new LilyGo T4-S3 QSPI transport driver.
     pub fn new(qspi: SpiDmaBus<'static, Blocking>) -> Self {
-        Lgt4s3Driver { qspi }
+        Lgt4s3Driver {
+            bus: Some(Bus::Idle(qspi)),
+            command_width: CommandWidth::Bits8,
+            address_width: AddressWidth::Bits24,
+            chunk_size: DMA_CHUNK_SIZE,
+            control_opcode: QSPI_CONTROL_OPCODE,
+            pixel_opcode: QSPI_PIXEL_OPCODE,
+        }
+    }
+
+    /// Sets the QSPI opcodes used for command writes and pixel writes.
+    ///
+    /// Defaults to [`QSPI_CONTROL_OPCODE`] and [`QSPI_PIXEL_OPCODE`].
+    pub fn with_opcodes(mut self, control: u8, pixel: u8) -> Self {
+        self.control_opcode = control;
+        self.pixel_opcode = pixel;
+        self
+    }
+
+    /// Sets the width of the QSPI command phase.
+    pub fn with_command_width(mut self, width: CommandWidth) -> Self {
+        self.command_width = width;
+        self
+    }
+
+    /// Sets the width of the QSPI address phase.
+    pub fn with_address_width(mut self, width: AddressWidth) -> Self {
+        self.address_width = width;
+        self
+    }
+
+    /// Sets the maximum number of pixel bytes sent per DMA transfer.
+    ///
+    /// Must be no larger than the DMA buffer the bus was created with.
+    pub fn with_chunk_size(mut self, chunk_size: NonZeroUsize) -> Self {
+        self.chunk_size = chunk_size.get();
+        self
+    }
+
+    /// Waits for any deferred transfer and returns the QSPI bus.
+    pub fn into_bus(mut self) -> SpiDmaBus<'static, Blocking> {
+        self.finish_transfer();
+        match self.bus.take() {
+            Some(Bus::Idle(bus)) => bus,
+            _ => unreachable!("bus is idle after finishing the transfer"),
+        }
+    }
+
+    /// Waits for a running deferred transfer, if any, and puts the bus back together.
+    fn finish_transfer(&mut self) {
+        self.bus = match self.bus.take() {
+            Some(Bus::Busy(transfer, rx_buffer)) => {
+                let (spi, tx_buffer) = transfer.wait();
+                Some(Bus::Idle(spi.with_buffers(rx_buffer, tx_buffer)))
+            }
+            bus => bus,
+        };
+    }
+
+    /// Returns the idle bus, first waiting for any deferred transfer.
+    fn qspi(&mut self) -> &mut SpiDmaBus<'static, Blocking> {
+        self.finish_transfer();
+        match self.bus.as_mut() {
+            Some(Bus::Idle(bus)) => bus,
+            _ => unreachable!("bus is idle after finishing the transfer"),
+        }
+    }
+
+    /// Builds the command phase for the given QSPI opcode.
+    fn command(&self, opcode: u8) -> Command {
+        match self.command_width {
+            CommandWidth::Bits8 => Command::_8Bit(opcode as u16, DataMode::Single),
+            CommandWidth::Bits16 => Command::_16Bit(opcode as u16, DataMode::Single),
+        }
+    }
+
+    /// Builds the address phase carrying the given DCS command.
+    fn address(&self, cmd: u32) -> Address {
+        let address_value = cmd << 8;
+        match self.address_width {
+            AddressWidth::Bits16 => Address::_16Bit(address_value, DataMode::Single),
+            AddressWidth::Bits24 => Address::_24Bit(address_value, DataMode::Single),
+            AddressWidth::Bits32 => Address::_32Bit(address_value, DataMode::Single),
+        }
     }
 }
 
@@ -33,28 +172,31 @@ impl ControllerInterface for Lgt4s3Driver {
     type Error = SpiError;
 
     fn send_command(&mut self, cmd: u8) -> Result<(), Self::Error> {
-        let address_value = (cmd as u32) << 8;
-
-        self.qspi.half_duplex_write(
-            DataMode::Single,
-            Command::_8Bit(QSPI_CONTROL_OPCODE as u16, DataMode::Single),
-            Address::_24Bit(address_value, DataMode::Single),
-            0,
-            &[],
-        )?;
+        let command = self.command(self.control_opcode);
+        let address = self.address(cmd as u32);
+
+        self.qspi()
+            .half_duplex_write(DataMode::Single, command, address, 0, &[])?;
         Ok(())
     }
 
     fn send_command_with_data(&mut self, cmd: u8, data: &[u8]) -> Result<(), Self::Error> {
-        let address_value = (cmd as u32) << 8;
-
-        self.qspi.half_duplex_write(
-            DataMode::Single,
-            Command::_8Bit(QSPI_CONTROL_OPCODE as u16, DataMode::Single),
-            Address::_24Bit(address_value, DataMode::Single),
-            0,
-            data,
-        )?;
+        let command = self.command(self.control_opcode);
+        let address = self.address(cmd as u32);
+
+        self.qspi()
+            .half_duplex_write(DataMode::Single, command, address, 0, data)?;
+        Ok(())
+    }
+
+    fn send_sequence(&mut self, seq: &[(u8, &[u8])]) -> Result<(), Self::Error> {
+        // Every entry shares the control opcode, so the command phase is built once.
+        let command = self.command(self.control_opcode);
+        for &(cmd, data) in seq {
+            let address = self.address(cmd as u32);
+            self.qspi()
+                .half_duplex_write(DataMode::Single, command, address, 0, data)?;
+        }
         Ok(())
     }
 
@@ -64,71 +206,90 @@ impl ControllerInterface for Lgt4s3Driver {
     }
 
     fn send_pixels_start(&mut self, pixels: &[u8]) -> Result<(), Self::Error> {
-        let ramwr_addr_val = (CMD_RAMWR as u32) << 8;
-        let ramwrc_addr_val = (CMD_RAMWRC as u32) << 8;
-
-        for (index, chunk) in pixels.chunks(DMA_CHUNK_SIZE).enumerate() {
-            if index == 0 {
-                self.qspi.half_duplex_write(
-                    DataMode::Quad,
-                    Command::_8Bit(QSPI_PIXEL_OPCODE as u16, DataMode::Single),
-                    Address::_24Bit(ramwr_addr_val, DataMode::Single),
-                    0,
-                    chunk,
-                )?;
+        for (index, chunk) in pixels.chunks(self.chunk_size).enumerate() {
+            let command = self.command(self.pixel_opcode);
+            let address = if index == 0 {
+                self.address(CMD_RAMWR)
             } else {
-                self.qspi.half_duplex_write(
-                    DataMode::Quad,
-                    Command::_8Bit(QSPI_PIXEL_OPCODE as u16, DataMode::Single),
-                    Address::_24Bit(ramwrc_addr_val, DataMode::Single),
-                    0,
-                    chunk,
-                )?;
-            }
+                self.address(CMD_RAMWRC)
+            };
+            self.qspi()
+                .half_duplex_write(DataMode::Quad, command, address, 0, chunk)?;
         }
         Ok(())
     }
 
     fn send_pixels_continue(&mut self, pixels: &[u8]) -> Result<(), Self::Error> {
-        let ramwrc_addr_val = (CMD_RAMWRC as u32) << 8;
-
-        for chunk in pixels.chunks(DMA_CHUNK_SIZE) {
-            self.qspi.half_duplex_write(
-                DataMode::Quad,
-                Command::_8Bit(QSPI_PIXEL_OPCODE as u16, DataMode::Single),
-                Address::_24Bit(ramwrc_addr_val, DataMode::Single),
-                0,
-                chunk,
-            )?;
+        for chunk in pixels.chunks(self.chunk_size) {
+            let command = self.command(self.pixel_opcode);
+            let address = self.address(CMD_RAMWRC);
+            self.qspi()
+                .half_duplex_write(DataMode::Quad, command, address, 0, chunk)?;
         }
         Ok(())
     }
-}
 
-/// GPIO Reset Pin
-pub struct ResetDriver<OUT> {
-    output: OUT,
-}
+    fn send_pixels_deferred(&mut self, pixels: &[u8]) -> Result<(), Self::Error> {
+        if pixels.is_empty() {
+            return Ok(());
+        }
+        // Everything before the last chunk is sent blocking, since `pixels`
+        // cannot be borrowed past this call; the last chunk is copied into
+        // the DMA buffer and left running.
+        let split = (pixels.len() - 1) / self.chunk_size * self.chunk_size;
+        let (head, tail) = pixels.split_at(split);
+        if !head.is_empty() {
+            self.send_pixels_start(head)?;
+        }
+        let command = self.command(self.pixel_opcode);
+        let address = if head.is_empty() {
+            self.address(CMD_RAMWR)
+        } else {
+            self.address(CMD_RAMWRC)
+        };
 
-impl<OUT> ResetDriver<OUT> {
-    /// Creates a new reset driver from a digital output pin.
-    pub fn new(output: OUT) -> Self {
-        ResetDriver { output }
+        self.finish_transfer();
+        let Some(Bus::Idle(bus)) = self.bus.take() else {
+            unreachable!("bus is idle after finishing the transfer");
+        };
+        let (spi, rx_buffer, mut tx_buffer) = bus.split();
+        tx_buffer.fill(tail);
+        match spi.half_duplex_write(DataMode::Quad, command, address, 0, tail.len(), tx_buffer) {
+            Ok(transfer) => {
+                self.bus = Some(Bus::Busy(transfer, rx_buffer));
+                Ok(())
+            }
+            Err((error, spi, tx_buffer)) => {
+                self.bus = Some(Bus::Idle(spi.with_buffers(rx_buffer, tx_buffer)));
+                Err(error)
+            }
+        }
+    }
+
+    fn is_transfer_complete(&mut self) -> bool {
+        match &self.bus {
+            Some(Bus::Busy(transfer, _)) => transfer.is_done(),
+            _ => true,
+        }
+    }
+
+    fn wait_transfer_complete(&mut self) -> Result<(), Self::Error> {
+        self.finish_transfer();
+        Ok(())
+    }
+
+    fn supports_quad(&self) -> bool {
+        true
     }
 }
 
-impl<OUT> ResetInterface for ResetDriver<OUT>
-where
-    OUT: embedded_hal::digital::OutputPin,
-{
-    type Error = OUT::Error;
-
-    fn reset(&mut self) -> Result<(), Self::Error> {
-        let delay = Delay::new();
-        self.output.set_low()?;
-        delay.delay_millis(20);
-        self.output.set_high()?;
-        delay.delay_millis(150);
+impl ControllerReadInterface for Lgt4s3Driver {
+    fn read_command(&mut self, cmd: u8, buf: &mut [u8]) -> Result<(), Self::Error> {
+        let command = self.command(QSPI_READ_OPCODE);
+        let address = self.address(cmd as u32);
+
+        self.qspi()
+            .half_duplex_read(DataMode::Single, command, address, 0, buf)?;
         Ok(())
     }
 }
@@ -0,0 +1,107 @@
+//! Reset through an I2C GPIO expander.
+//!
+//! Several AMOLED carrier boards route the RM690B0 reset line to a
+//! PCA9535/TCA9534-style port expander instead of a microcontroller GPIO.
+
+use crate::ResetInterface;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::I2c;
+
+/// Error returned when the reset pin is out of range for the expander.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidPin;
+
+/// Drives the reset line through a bit of an I2C GPIO expander.
+///
+/// The reset sequence holds the line low for 20ms, then high for 150ms
+/// before the controller accepts commands. Other expander outputs keep their
+/// current level.
+pub struct ExpanderResetDriver<I2C, DELAY> {
+    i2c: I2C,
+    delay: DELAY,
+    address: u8,
+    output_register: u8,
+    config_register: u8,
+    mask: u8,
+}
+
+impl<I2C, DELAY> ExpanderResetDriver<I2C, DELAY>
+where
+    I2C: I2c,
+    DELAY: DelayNs,
+{
+    /// Creates a reset driver for an 8-bit expander (TCA9534/PCA9554 register layout).
+    ///
+    /// `pin` is the expander output connected to reset, `0..8`.
+    pub fn new(i2c: I2C, delay: DELAY, address: u8, pin: u8) -> Result<Self, InvalidPin> {
+        if pin >= 8 {
+            return Err(InvalidPin);
+        }
+        Ok(ExpanderResetDriver {
+            i2c,
+            delay,
+            address,
+            output_register: 0x01,
+            config_register: 0x03,
+            mask: 1 << pin,
+        })
+    }
+
+    /// Creates a reset driver for a 16-bit expander (PCA9535/TCA9535 register layout).
+    ///
+    /// `pin` is the expander output connected to reset: `0..8` for port 0
+    /// (P00-P07) and `8..16` for port 1 (P10-P17).
+    pub fn pca9535(i2c: I2C, delay: DELAY, address: u8, pin: u8) -> Result<Self, InvalidPin> {
+        if pin >= 16 {
+            return Err(InvalidPin);
+        }
+        let port = pin / 8;
+        Ok(ExpanderResetDriver {
+            i2c,
+            delay,
+            address,
+            output_register: 0x02 + port,
+            config_register: 0x06 + port,
+            mask: 1 << (pin % 8),
+        })
+    }
+
+    /// Releases the I2C bus and delay.
+    pub fn release(self) -> (I2C, DELAY) {
+        (self.i2c, self.delay)
+    }
+
+    /// Reads an expander register.
+    fn read_register(&mut self, register: u8) -> Result<u8, I2C::Error> {
+        let mut value = [0u8; 1];
+        self.i2c.write_read(self.address, &[register], &mut value)?;
+        Ok(value[0])
+    }
+
+    /// Writes an expander register.
+    fn write_register(&mut self, register: u8, value: u8) -> Result<(), I2C::Error> {
+        self.i2c.write(self.address, &[register, value])
+    }
+}
+
+impl<I2C, DELAY> ResetInterface for ExpanderResetDriver<I2C, DELAY>
+where
+    I2C: I2c,
+    DELAY: DelayNs,
+{
+    type Error = I2C::Error;
+
+    fn reset(&mut self) -> Result<(), Self::Error> {
+        let output = self.read_register(self.output_register)?;
+        let config = self.read_register(self.config_register)?;
+
+        // A cleared configuration bit makes the pin an output.
+        self.write_register(self.output_register, output & !self.mask)?;
+        self.write_register(self.config_register, config & !self.mask)?;
+        self.delay.delay_ms(20);
+        self.write_register(self.output_register, output | self.mask)?;
+        self.delay.delay_ms(150);
+        Ok(())
+    }
+}
@@ -1,6 +1,112 @@
-use crate::{ColorMode, ControllerInterface, DrawTarget, ResetInterface, Rm690b0Driver};
+use crate::{
+    ColorMode, ControllerInterface, DrawTarget, DriverError, Endianness, ResetInterface,
+    Rm690b0Driver, SoftwareRotation,
+};
 use embedded_graphics_core::pixelcolor::Rgb888;
 use embedded_graphics_core::prelude::*;
+use embedded_graphics_core::primitives::Rectangle;
+
+/// Encodes `rgb` into `out` using the byte layout of `mode`.
+///
+/// `out` must be exactly `mode.bytes_per_pixel()` bytes long.
+pub(crate) fn encode_color(mode: ColorMode, rgb: Rgb888, out: &mut [u8]) {
+    match mode {
+        ColorMode::Rgb888 => {
+            out[0] = rgb.r();
+            out[1] = rgb.g();
+            out[2] = rgb.b();
+        }
+        ColorMode::Rgb666 => {
+            // Store RGB666 left-aligned in each byte (6 MSBs used).
+            out[0] = rgb.r() & 0xFC;
+            out[1] = rgb.g() & 0xFC;
+            out[2] = rgb.b() & 0xFC;
+        }
+        ColorMode::Rgb565 => {
+            let r5 = (rgb.r() >> 3) as u16;
+            let g6 = (rgb.g() >> 2) as u16;
+            let b5 = (rgb.b() >> 3) as u16;
+            let packed = (r5 << 11) | (g6 << 5) | b5;
+
+            out[0] = (packed >> 8) as u8;
+            out[1] = (packed & 0xFF) as u8;
+        }
+        ColorMode::Gray8 => {
+            // Integer luma approximation: 0.299R + 0.587G + 0.114B.
+            let gray =
+                ((rgb.r() as u16 * 77) + (rgb.g() as u16 * 150) + (rgb.b() as u16 * 29)) >> 8;
+            out[0] = gray as u8;
+        }
+    }
+}
+
+/// How drawing stores colors in the framebuffer.
+#[derive(Clone, Copy)]
+pub(crate) struct PixelEncoder {
+    mode: ColorMode,
+    bgr: bool,
+    swap_bytes: bool,
+}
+
+impl PixelEncoder {
+    /// Creates an encoder for `mode`, with `bgr` and `endianness` applied where they make sense.
+    pub(crate) fn new(mode: ColorMode, bgr: bool, endianness: Endianness) -> Self {
+        PixelEncoder {
+            mode,
+            bgr: bgr && mode != ColorMode::Gray8,
+            swap_bytes: mode == ColorMode::Rgb565 && endianness == Endianness::Little,
+        }
+    }
+
+    /// Encodes `rgb` into `out`, which must be `mode.bytes_per_pixel()` bytes long.
+    pub(crate) fn encode(&self, rgb: Rgb888, out: &mut [u8]) {
+        let rgb = if self.bgr {
+            Rgb888::new(rgb.b(), rgb.g(), rgb.r())
+        } else {
+            rgb
+        };
+        encode_color(self.mode, rgb, out);
+        if self.swap_bytes {
+            out.swap(0, 1);
+        }
+    }
+
+    /// Decodes a pixel stored by [`Self::encode`], expanding it back to RGB888.
+    pub(crate) fn decode(&self, bytes: &[u8]) -> Rgb888 {
+        let rgb = match self.mode {
+            ColorMode::Rgb888 | ColorMode::Rgb666 => Rgb888::new(bytes[0], bytes[1], bytes[2]),
+            ColorMode::Rgb565 => {
+                let packed = if self.swap_bytes {
+                    u16::from_le_bytes([bytes[0], bytes[1]])
+                } else {
+                    u16::from_be_bytes([bytes[0], bytes[1]])
+                };
+                let r5 = (packed >> 11) as u8;
+                let g6 = ((packed >> 5) & 0x3F) as u8;
+                let b5 = (packed & 0x1F) as u8;
+                Rgb888::new(r5 << 3 | r5 >> 2, g6 << 2 | g6 >> 4, b5 << 3 | b5 >> 2)
+            }
+            ColorMode::Gray8 => Rgb888::new(bytes[0], bytes[0], bytes[0]),
+        };
+        if self.bgr {
+            Rgb888::new(rgb.b(), rgb.g(), rgb.r())
+        } else {
+            rgb
+        }
+    }
+}
+
+/// Mixes `over` onto `under`, with `alpha` 0xFF giving `over` and 0x00 giving `under`.
+fn blend(under: Rgb888, over: Rgb888, alpha: u8) -> Rgb888 {
+    let mix = |under: u8, over: u8| {
+        ((over as u16 * alpha as u16 + under as u16 * (0xFF - alpha as u16) + 0x7F) / 0xFF) as u8
+    };
+    Rgb888::new(
+        mix(under.r(), over.r()),
+        mix(under.g(), over.g()),
+        mix(under.b(), over.b()),
+    )
+}
 
 impl<IFACE, RST, C> DrawTarget for Rm690b0Driver<IFACE, RST, C>
 where
@@ -14,62 +120,266 @@ where
     type Error = core::convert::Infallible;
 
     /// Draws a single pixel to the internal framebuffer.
+    ///
+    /// Pixels outside the display are ignored. A pixel inside the display
+    /// that doesn't fit in the framebuffer points at a misconfigured size;
+    /// it triggers a debug assertion and is dropped in release builds.
     fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
         let bytes_per_pixel = self.color_mode.bytes_per_pixel();
+        let encoder = self.pixel_encoder();
+        let (width, height) = self.draw_dimensions();
+        let fb_width = self.dimensions().0 as u32;
+        let mut dirty: Option<(u16, u16, u16, u16)> = None;
 
         for Pixel(coord, color) in pixels.into_iter() {
-            if coord.x >= 0
-                && coord.x < self.config.width as i32
-                && coord.y >= 0
-                && coord.y < self.config.height as i32
-            {
-                let x = coord.x as u32;
-                let y = coord.y as u32;
-                let index = ((y * self.config.width as u32 + x) as usize) * bytes_per_pixel;
+            if coord.x >= 0 && coord.x < width as i32 && coord.y >= 0 && coord.y < height as i32 {
+                let (x, y) = self.to_framebuffer(coord.x as u16, coord.y as u16);
+                let (x, y) = (x as u32, y as u32);
+                let index = ((y * fb_width + x) as usize) * bytes_per_pixel;
                 let pixel_end = index + bytes_per_pixel;
+                debug_assert!(
+                    pixel_end <= self.framebuffer.len(),
+                    "framebuffer shorter than width * height * bytes per pixel"
+                );
 
                 if pixel_end <= self.framebuffer.len() {
                     // Convert from generic color into Rgb888
-                    let rgb: Rgb888 = color.into();
-
-                    match self.color_mode {
-                        ColorMode::Rgb888 => {
-                            self.framebuffer[index] = rgb.r();
-                            self.framebuffer[index + 1] = rgb.g();
-                            self.framebuffer[index + 2] = rgb.b();
-                        }
-                        ColorMode::Rgb666 => {
-                            // Store RGB666 left-aligned in each byte (6 MSBs used).
-                            self.framebuffer[index] = rgb.r() & 0xFC;
-                            self.framebuffer[index + 1] = rgb.g() & 0xFC;
-                            self.framebuffer[index + 2] = rgb.b() & 0xFC;
-                        }
-                        ColorMode::Rgb565 => {
-                            let r5 = (rgb.r() >> 3) as u16;
-                            let g6 = (rgb.g() >> 2) as u16;
-                            let b5 = (rgb.b() >> 3) as u16;
-                            let packed = (r5 << 11) | (g6 << 5) | b5;
-
-                            self.framebuffer[index] = (packed >> 8) as u8;
-                            self.framebuffer[index + 1] = (packed & 0xFF) as u8;
-                        }
-                        ColorMode::Gray8 => {
-                            // Integer luma approximation: 0.299R + 0.587G + 0.114B.
-                            let gray = ((rgb.r() as u16 * 77)
-                                + (rgb.g() as u16 * 150)
-                                + (rgb.b() as u16 * 29))
-                                >> 8;
-                            self.framebuffer[index] = gray as u8;
-                        }
-                    }
+                    encoder.encode(color.into(), &mut self.framebuffer[index..pixel_end]);
+
+                    let (x, y) = (x as u16, y as u16);
+                    self.mark_row_dirty(y);
+                    dirty = Some(match dirty {
+                        Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+                        None => (x, y, x, y),
+                    });
                 }
             }
         }
+        if let Some((x_start, y_start, x_end, y_end)) = dirty {
+            self.extend_dirty_bounds(x_start, y_start, x_end, y_end);
+        }
         Ok(())
     }
+
+    /// Writes a row-major stream of colors into `area`, one framebuffer row at a time.
+    ///
+    /// Pixels of `area` that fall outside the display still consume a color,
+    /// keeping the iterator in step with the area. With a software rotation
+    /// the rows no longer match the framebuffer, so pixels are drawn one by one.
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        if self.software_rotation() != SoftwareRotation::None {
+            return self.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .map(|(point, color)| Pixel(point, color)),
+            );
+        }
+
+        let clipped = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = clipped.bottom_right() else {
+            return Ok(());
+        };
+
+        let bytes_per_pixel = self.color_mode.bytes_per_pixel();
+        let encoder = self.pixel_encoder();
+        let stride = self.dimensions().0 as usize * bytes_per_pixel;
+        let area_width = area.size.width as usize;
+        // Colors skipped at the start and end of each row to reach the clipped columns.
+        let skip_left = (clipped.top_left.x - area.top_left.x) as usize;
+        let visible = clipped.size.width as usize;
+        let skip_right = area_width - skip_left - visible;
+
+        let mut colors = colors.into_iter();
+        // Skip the rows above the display.
+        let rows_above = (clipped.top_left.y - area.top_left.y) as usize;
+        for _ in 0..rows_above * area_width {
+            if colors.next().is_none() {
+                return Ok(());
+            }
+        }
+
+        let mut last_row = None;
+        'rows: for y in clipped.top_left.y as usize..=bottom_right.y as usize {
+            for _ in 0..skip_left {
+                if colors.next().is_none() {
+                    break 'rows;
+                }
+            }
+            let start = y * stride + clipped.top_left.x as usize * bytes_per_pixel;
+            let end = start + visible * bytes_per_pixel;
+            debug_assert!(
+                end <= self.framebuffer.len(),
+                "framebuffer shorter than width * height * bytes per pixel"
+            );
+            if end > self.framebuffer.len() {
+                break;
+            }
+            last_row = Some(y as u16);
+            for pixel in self.framebuffer[start..end].chunks_exact_mut(bytes_per_pixel) {
+                let Some(color) = colors.next() else {
+                    break 'rows;
+                };
+                encoder.encode(color.into(), pixel);
+            }
+            for _ in 0..skip_right {
+                if colors.next().is_none() {
+                    break 'rows;
+                }
+            }
+        }
+        // Consume the rows below the display, as the per-pixel fallback does.
+        let rows_below = (area.bottom_right().map_or(0, |p| p.y) - bottom_right.y) as usize;
+        colors.take(rows_below * area_width).for_each(drop);
+
+        if let Some(y_end) = last_row {
+            self.mark_dirty(
+                clipped.top_left.x as u16,
+                clipped.top_left.y as u16,
+                bottom_right.x as u16,
+                y_end,
+            );
+        }
+        Ok(())
+    }
+
+    /// Fills a rectangle by encoding the color once and copying it row by row.
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+        // A rotated rectangle is still a rectangle; map its corners into the framebuffer.
+        let (x0, y0) = self.to_framebuffer(area.top_left.x as u16, area.top_left.y as u16);
+        let (x1, y1) = self.to_framebuffer(bottom_right.x as u16, bottom_right.y as u16);
+        let area = Rectangle::with_corners(
+            Point::new(x0.min(x1) as i32, y0.min(y1) as i32),
+            Point::new(x0.max(x1) as i32, y0.max(y1) as i32),
+        );
+        let bottom_right = area.bottom_right().unwrap_or(area.top_left);
+
+        let bytes_per_pixel = self.color_mode.bytes_per_pixel();
+        let mut encoded = [0u8; 3];
+        let encoded = &mut encoded[..bytes_per_pixel];
+        self.pixel_encoder().encode(color.into(), encoded);
+
+        self.mark_dirty(
+            area.top_left.x as u16,
+            area.top_left.y as u16,
+            bottom_right.x as u16,
+            bottom_right.y as u16,
+        );
+
+        let stride = self.dimensions().0 as usize * bytes_per_pixel;
+        let row_start = area.top_left.x as usize * bytes_per_pixel;
+        let row_end = (bottom_right.x as usize + 1) * bytes_per_pixel;
+        for y in area.top_left.y as usize..=bottom_right.y as usize {
+            let start = y * stride + row_start;
+            let end = y * stride + row_end;
+            debug_assert!(
+                end <= self.framebuffer.len(),
+                "framebuffer shorter than width * height * bytes per pixel"
+            );
+            if end > self.framebuffer.len() {
+                break;
+            }
+            for pixel in self.framebuffer[start..end].chunks_exact_mut(bytes_per_pixel) {
+                pixel.copy_from_slice(encoded);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<IFACE, RST, C> Rm690b0Driver<IFACE, RST, C>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor + Into<Rgb888>,
+{
+    /// Writes one pixel to the framebuffer, in drawing coordinates.
+    ///
+    /// Returns an error if `(x, y)` lies outside the display.
+    pub fn set_pixel(
+        &mut self,
+        x: u16,
+        y: u16,
+        color: C,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let (width, height) = self.draw_dimensions();
+        if x >= width || y >= height {
+            return Err(DriverError::InvalidConfiguration("Pixel out of bounds"));
+        }
+        let Ok(()) = self.draw_iter([Pixel(Point::new(x as i32, y as i32), color)]);
+        Ok(())
+    }
+
+    /// Draws pixels blended over the framebuffer contents with opacity `alpha`.
+    ///
+    /// Each stored pixel is decoded, mixed with the new color (0xFF is
+    /// opaque, 0x00 leaves the framebuffer unchanged) and stored again, so
+    /// RGB565, RGB666 and Gray8 framebuffers blend at their own precision.
+    /// Pixels outside the display are ignored, as in `draw_iter`.
+    pub fn draw_blended<I>(&mut self, pixels: I, alpha: u8)
+    where
+        I: IntoIterator<Item = Pixel<C>>,
+    {
+        let bytes_per_pixel = self.color_mode.bytes_per_pixel();
+        let encoder = self.pixel_encoder();
+        let (width, height) = self.draw_dimensions();
+        let fb_width = self.dimensions().0 as usize;
+        let mut dirty: Option<(u16, u16, u16, u16)> = None;
+
+        for Pixel(coord, color) in pixels {
+            if coord.x < 0 || coord.x >= width as i32 || coord.y < 0 || coord.y >= height as i32 {
+                continue;
+            }
+            let (x, y) = self.to_framebuffer(coord.x as u16, coord.y as u16);
+            let index = (y as usize * fb_width + x as usize) * bytes_per_pixel;
+            let Some(pixel) = self.framebuffer.get_mut(index..index + bytes_per_pixel) else {
+                continue;
+            };
+            let blended = blend(encoder.decode(pixel), color.into(), alpha);
+            encoder.encode(blended, pixel);
+
+            self.mark_row_dirty(y);
+            dirty = Some(match dirty {
+                Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+                None => (x, y, x, y),
+            });
+        }
+        if let Some((x_start, y_start, x_end, y_end)) = dirty {
+            self.extend_dirty_bounds(x_start, y_start, x_end, y_end);
+        }
+    }
+
+    /// Writes one pixel to the framebuffer and sends it straight to the panel.
+    ///
+    /// Windows must be even-aligned, so the 2x2 cell containing the pixel
+    /// is sent, taking the other three pixels from the framebuffer. Cheaper
+    /// than a partial flush for sparse updates such as a blinking cursor.
+    /// The dirty state still includes the pixel.
+    pub fn push_pixel(
+        &mut self,
+        x: u16,
+        y: u16,
+        color: C,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.set_pixel(x, y, color)?;
+        let (x, y) = self.to_framebuffer(x, y);
+        let (width, height) = self.dimensions();
+        self.flush_bounds(
+            x & !1,
+            y & !1,
+            (x | 1).min(width - 1),
+            (y | 1).min(height - 1),
+        )
+    }
 }
 
 impl<IFACE, RST, C> OriginDimensions for Rm690b0Driver<IFACE, RST, C>
@@ -79,6 +389,7 @@ where
     C: PixelColor,
 {
     fn size(&self) -> Size {
-        Size::new((self.config.width) as u32, (self.config.height) as u32)
+        let (width, height) = self.draw_dimensions();
+        Size::new(width as u32, height as u32)
     }
 }
@@ -9,15 +9,19 @@
 //! Additionally, the reset pin is controlled via GPIO or I2C GPIO expander.
 //!
 //! The driver currently supports the Lilygo T4-S3 AMOLED display out of the box, but can be extended to support other displays.
+//! Modules wired for plain 4-wire SPI with a D/C pin can use the generic `SpiInterface`.
+//! Boards without memory for a framebuffer can use `Rm690b0DirectDriver`, which draws straight to display RAM.
 //!
 //! ## Usage
 //! 1. Implement the `ControllerInterface` trait for the controller driving interface Ex. QSPI
 //! 2. Implement the `ResetInterface` trait for the Reset pin.
-//! 3. Create a `Rm690b0Driver` instance with the display interface and reset pin.
+//! 3. Create a `Rm690b0Driver` instance with the display interface and reset pin, either with
+//!    `new_heap`/`new_static` or through `Rm690b0Builder` for further options.
 //! 4. Use the driver to draw using `embedded-graphics`.
 //!
 //! If you are going to use heap allocated framebuffer, you will need to make sure that an allocator is available in your environment.
 //! In some crates this is done by enabling the `alloc` feature.
+//! Targets without an allocator can disable this crate's default `alloc` feature and use `new_static`.
 //!
 //! ## Feature Flags
 #![doc = document_features::document_features!()]
@@ -36,40 +40,359 @@
 //! ```
 //!
 
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 #[cfg(feature = "lilygo_t4_s3")]
 pub mod displays;
 
 #[cfg(feature = "lilygo_t4_s3")]
 pub use displays::lilygo_t4_s3::*;
 
+#[cfg(feature = "async")]
+pub mod asynch;
+
+#[cfg(feature = "async")]
+pub use asynch::*;
+
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+
+#[cfg(any(test, feature = "alloc"))]
 extern crate alloc;
 
+mod builder;
+mod direct;
+mod expander;
 mod graphics_core;
+mod power;
+mod reset;
+mod spi;
+mod sub_view;
+#[cfg(all(test, feature = "alloc"))]
+mod tests;
+mod with_delay;
+
+pub use builder::Rm690b0Builder;
+pub use direct::Rm690b0DirectDriver;
+pub use expander::{ExpanderResetDriver, InvalidPin};
+use graphics_core::PixelEncoder;
+pub use power::{PowerPin, PoweredReset, PoweredResetError};
+pub use reset::ResetDriver;
+pub use spi::{SpiInterface, SpiInterfaceError};
+pub use sub_view::SubView;
+pub use with_delay::Rm690b0DriverWithDelay;
 
+#[cfg(feature = "alloc")]
 use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::ops::Range;
 use embedded_graphics::prelude::PixelColor;
 use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_graphics_core::geometry::{Point, Size};
+use embedded_graphics_core::pixelcolor::{Gray8, Rgb565, Rgb888};
+use embedded_graphics_core::primitives::Rectangle;
 use embedded_hal::delay::DelayNs;
 
+/// Number of columns addressable in the RM690B0 display RAM.
+pub const MAX_COLUMNS: u16 = 480;
+
+/// Number of rows addressable in the RM690B0 display RAM.
+pub const MAX_ROWS: u16 = 600;
+
+/// Number of `u32` words in the fixed-size dirty row bitmap used without `alloc`,
+/// one bit per row of the larger RAM dimension.
+#[cfg(not(feature = "alloc"))]
+const DIRTY_ROW_WORDS: usize = (if MAX_ROWS > MAX_COLUMNS {
+    MAX_ROWS
+} else {
+    MAX_COLUMNS
+} as usize)
+    .div_ceil(32);
+
+/// One bit per logical row drawn since the last dirty flush, in either orientation.
+#[cfg(feature = "alloc")]
+pub(crate) type DirtyRows = Box<[u32]>;
+/// One bit per logical row drawn since the last dirty flush, in either orientation.
+#[cfg(not(feature = "alloc"))]
+pub(crate) type DirtyRows = [u32; DIRTY_ROW_WORDS];
+
+/// Returns a cleared dirty row bitmap for `config`, or `None` if the panel has
+/// more rows, in either orientation, than the fixed-size bitmap can track.
+pub(crate) fn dirty_rows_for(config: DisplaySize) -> Option<DirtyRows> {
+    let words = (config.width.max(config.height) as usize).div_ceil(32);
+    #[cfg(feature = "alloc")]
+    {
+        Some(alloc::vec![0; words].into_boxed_slice())
+    }
+    #[cfg(not(feature = "alloc"))]
+    {
+        (words <= DIRTY_ROW_WORDS).then_some([0; DIRTY_ROW_WORDS])
+    }
+}
+
+/// Bytes in a stack buffer holding one window row: the longest side at 3 bytes per pixel.
+pub(crate) const ROW_BUFFER_LEN: usize = (if MAX_ROWS > MAX_COLUMNS {
+    MAX_ROWS
+} else {
+    MAX_COLUMNS
+} as usize)
+    * 3;
+
 /// Configuration for the display dimensions.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct DisplaySize {
     /// Display width in pixels.
     pub width: u16,
     /// Display height in pixels.
     pub height: u16,
+    /// First display RAM column of the visible area.
+    pub col_offset: u16,
+    /// First display RAM row of the visible area.
+    pub row_offset: u16,
 }
 
 impl DisplaySize {
-    /// Creates a new display size in pixels.
+    /// Creates a new display size in pixels, with the visible area starting at RAM origin.
+    ///
+    /// `width` and `height` must be non-zero and at most [`MAX_COLUMNS`] and
+    /// [`MAX_ROWS`]; this is not checked. Use [`Self::try_new`] for sizes
+    /// that are not known to be valid.
     pub const fn new(width: u16, height: u16) -> Self {
-        DisplaySize { width, height }
+        DisplaySize {
+            width,
+            height,
+            col_offset: 0,
+            row_offset: 0,
+        }
+    }
+
+    /// Creates a new display size, rejecting zero dimensions and sizes beyond the controller's RAM.
+    pub const fn try_new(width: u16, height: u16) -> Result<Self, &'static str> {
+        if width == 0 || height == 0 {
+            return Err("Display dimensions must be non-zero");
+        }
+        if width > MAX_COLUMNS || height > MAX_ROWS {
+            return Err("Display dimensions exceed the RM690B0 display RAM");
+        }
+        Ok(Self::new(width, height))
+    }
+
+    /// Returns the number of pixels on the display.
+    pub const fn area(&self) -> u32 {
+        self.width as u32 * self.height as u32
+    }
+
+    /// Returns the width divided by the height.
+    pub fn aspect_ratio(&self) -> f32 {
+        self.width as f32 / self.height as f32
+    }
+
+    /// Sets the display RAM column and row where the visible area starts.
+    ///
+    /// Needed for modules whose glass does not begin at RAM address 0. The
+    /// offsets are added to CASET/RASET, while drawing coordinates stay zero-based.
+    pub const fn with_offset(mut self, col_offset: u16, row_offset: u16) -> Self {
+        self.col_offset = col_offset;
+        self.row_offset = row_offset;
+        self
+    }
+
+    /// Returns the logical `(width, height)` in `orientation`.
+    pub(crate) const fn logical_size(&self, orientation: Orientation) -> (u16, u16) {
+        if orientation.is_landscape() {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    /// Returns the RAM offset of the visible area along the logical x and y axes in `orientation`.
+    pub(crate) const fn ram_offset(&self, orientation: Orientation) -> (u16, u16) {
+        if orientation.is_landscape() {
+            (self.row_offset, self.col_offset)
+        } else {
+            (self.col_offset, self.row_offset)
+        }
+    }
+}
+
+/// Parameters of the commands sent around the manufacturer init sequence.
+///
+/// Shared by every driver variant, so they all initialize the panel the
+/// same way: MADCTR before the manufacturer sequence, then COLMOD, the
+/// tearing effect, WRCTRLD, WRDISBV and DISPON after it.
+pub(crate) struct InitCommands {
+    madctr: [u8; 1],
+    colmod: [u8; 1],
+    ctrl: [u8; 1],
+    brightness: [u8; 1],
+    tear_mode: Option<TearMode>,
+}
+
+impl InitCommands {
+    pub(crate) fn new(
+        madctr: u8,
+        color_mode: ColorMode,
+        tear_mode: Option<TearMode>,
+        brightness: u8,
+    ) -> Self {
+        InitCommands {
+            madctr: [madctr],
+            colmod: [color_mode.colmod()],
+            ctrl: [DisplayControl::default().bits()],
+            brightness: [brightness],
+            tear_mode,
+        }
+    }
+
+    /// Returns the batch sent before the manufacturer sequence.
+    pub(crate) fn head(&self) -> [(u8, &[u8]); 1] {
+        [(commands::MADCTR, &self.madctr)]
+    }
+
+    /// Returns the batch sent after the manufacturer sequence, ending with DISPON.
+    ///
+    /// The brightness is written before DISPON, so the panel never lights up
+    /// at a different level first.
+    pub(crate) fn tail(&self) -> [(u8, &[u8]); 5] {
+        [
+            (commands::COLMOD, &self.colmod),
+            TearMode::command(self.tear_mode),
+            (commands::WRCTRLD, &self.ctrl),
+            (commands::WRDISBV, &self.brightness),
+            (commands::DISPON, &[]),
+        ]
+    }
+
+    /// Sends the head, `init_sequence` and the tail, one [`ControllerInterface::send_sequence`] each.
+    ///
+    /// Three batches, since the manufacturer sequence has to be spliced in
+    /// without allocating.
+    pub(crate) fn send<IFACE: ControllerInterface>(
+        &self,
+        interface: &mut IFACE,
+        init_sequence: InitSequence,
+    ) -> Result<(), IFACE::Error> {
+        interface.send_sequence(&self.head())?;
+        interface.send_sequence(init_sequence)?;
+        interface.send_sequence(&self.tail())
     }
 }
 
+/// Encodes an inclusive address range as the parameters of CASET, RASET or PTLAR.
+///
+/// Also used for the first two of the three big-endian values of VSCRDEF.
+pub(crate) const fn range_params(start: u16, end: u16) -> [u8; 4] {
+    let [start_high, start_low] = start.to_be_bytes();
+    let [end_high, end_low] = end.to_be_bytes();
+    [start_high, start_low, end_high, end_low]
+}
+
+/// Checks an inclusive logical window and returns its CASET and RASET parameters.
+///
+/// `size` is the logical display size and `delta` is added to reach display
+/// RAM along each axis. In landscape the RAM limits are swapped to match
+/// the logical axes.
+pub(crate) fn window_params(
+    (x_start, y_start, x_end, y_end): (u16, u16, u16, u16),
+    (width, height): (u16, u16),
+    (delta_x, delta_y): (u16, u16),
+    landscape: bool,
+) -> Result<([u8; 4], [u8; 4]), &'static str> {
+    if x_end < x_start || y_end < y_start {
+        return Err("Window end must not be before its start");
+    }
+    if x_start % 2 != 0 || (x_end - x_start + 1) % 2 != 0 {
+        return Err("x_start and width must both be even");
+    }
+    if y_start % 2 != 0 || (y_end - y_start + 1) % 2 != 0 {
+        return Err("y_start and height must both be even");
+    }
+    if x_end >= width || y_end >= height {
+        return Err("Invalid window dimensions");
+    }
+    let (ram_columns, ram_rows) = if landscape {
+        (MAX_ROWS, MAX_COLUMNS)
+    } else {
+        (MAX_COLUMNS, MAX_ROWS)
+    };
+    if x_end + delta_x >= ram_columns || y_end + delta_y >= ram_rows {
+        return Err("Window offset past the end of display RAM");
+    }
+    Ok((
+        range_params(x_start + delta_x, x_end + delta_x),
+        range_params(y_start + delta_y, y_end + delta_y),
+    ))
+}
+
+/// Maps the partial area's panel rows `start..=end` to inclusive logical
+/// bounds, widened to even boundaries.
+///
+/// Rows follow the rotation documented on [`Orientation`]; `size` is the
+/// logical display size.
+pub(crate) fn partial_bounds(
+    (start, end): (u16, u16),
+    orientation: Orientation,
+    (width, height): (u16, u16),
+) -> (u16, u16, u16, u16) {
+    let (start, end) = match orientation {
+        Orientation::Portrait | Orientation::Landscape => (start, end),
+        Orientation::PortraitFlipped => (height - 1 - end, height - 1 - start),
+        Orientation::LandscapeFlipped => (width - 1 - end, width - 1 - start),
+    };
+    if orientation.is_landscape() {
+        (start & !1, 0, (end | 1).min(width - 1), height - 1)
+    } else {
+        (0, start & !1, width - 1, (end | 1).min(height - 1))
+    }
+}
+
+/// Widens inclusive bounds to the even boundaries windows need, staying within `size`.
+pub(crate) fn widen_to_even(
+    (x_start, y_start, x_end, y_end): (u16, u16, u16, u16),
+    (width, height): (u16, u16),
+) -> (u16, u16, u16, u16) {
+    (
+        x_start & !1,
+        y_start & !1,
+        (x_end | 1).min(width - 1),
+        (y_end | 1).min(height - 1),
+    )
+}
+
+/// Every [`ColorMode`], the default for `supported_color_modes`.
+pub(crate) const ALL_COLOR_MODES: &[ColorMode] = &[
+    ColorMode::Rgb565,
+    ColorMode::Rgb888,
+    ColorMode::Rgb666,
+    ColorMode::Gray8,
+];
+
 /// RM690B0 Driver Errors
+///
+/// New variants may be added, so matches need a wildcard arm. To propagate
+/// driver errors with `?`, implement `From` for the application's error
+/// type, mapping the inner errors as needed:
+///
+/// ```ignore
+/// enum AppError {
+///     Display,
+///     Config(&'static str),
+/// }
+///
+/// impl<I, R> From<DriverError<I, R>> for AppError {
+///     fn from(err: DriverError<I, R>) -> Self {
+///         match err {
+///             DriverError::InvalidConfiguration(msg) => AppError::Config(msg),
+///             _ => AppError::Display,
+///         }
+///     }
+/// }
+/// ```
 #[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum DriverError<InterfaceError, ResetError> {
     /// Error originating from the display interface (QSPI/SPI/I2C).
     InterfaceError(InterfaceError),
@@ -79,6 +402,59 @@ pub enum DriverError<InterfaceError, ResetError> {
     InvalidConfiguration(&'static str),
 }
 
+impl<InterfaceError, ResetError> DriverError<InterfaceError, ResetError> {
+    /// Converts the interface error with `f`, leaving other variants unchanged.
+    pub fn map_interface_err<E, F>(self, f: F) -> DriverError<E, ResetError>
+    where
+        F: FnOnce(InterfaceError) -> E,
+    {
+        match self {
+            DriverError::InterfaceError(err) => DriverError::InterfaceError(f(err)),
+            DriverError::ResetError(err) => DriverError::ResetError(err),
+            DriverError::InvalidConfiguration(msg) => DriverError::InvalidConfiguration(msg),
+        }
+    }
+
+    /// Converts the reset error with `f`, leaving other variants unchanged.
+    pub fn map_reset_err<E, F>(self, f: F) -> DriverError<InterfaceError, E>
+    where
+        F: FnOnce(ResetError) -> E,
+    {
+        match self {
+            DriverError::InterfaceError(err) => DriverError::InterfaceError(err),
+            DriverError::ResetError(err) => DriverError::ResetError(f(err)),
+            DriverError::InvalidConfiguration(msg) => DriverError::InvalidConfiguration(msg),
+        }
+    }
+}
+
+impl<InterfaceError, ResetError> core::fmt::Display for DriverError<InterfaceError, ResetError> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DriverError::InterfaceError(_) => f.write_str("display interface error"),
+            DriverError::ResetError(_) => f.write_str("reset error"),
+            DriverError::InvalidConfiguration(reason) => {
+                write!(f, "invalid configuration: {}", reason)
+            }
+        }
+    }
+}
+
+/// The inner interface or reset error is reported as the [`source`](core::error::Error::source).
+impl<InterfaceError, ResetError> core::error::Error for DriverError<InterfaceError, ResetError>
+where
+    InterfaceError: core::error::Error + 'static,
+    ResetError: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            DriverError::InterfaceError(e) => Some(e),
+            DriverError::ResetError(e) => Some(e),
+            DriverError::InvalidConfiguration(_) => None,
+        }
+    }
+}
+
 /// Trait to implement the controller communication interface (QSPI, SPI, etc.).
 pub trait ControllerInterface {
     /// The specific error type for this interface implementation.
@@ -93,6 +469,38 @@ pub trait ControllerInterface {
     /// Sends pixel data
     fn send_pixels(&mut self, pixels: &[u8]) -> Result<(), Self::Error>;
 
+    /// Sends a batch of commands, each with its (possibly empty) data.
+    ///
+    /// Defaults to one [`Self::send_command`] or
+    /// [`Self::send_command_with_data`] per entry. Implementations can
+    /// override it to cut per-transaction overhead, e.g. by keeping chip
+    /// select asserted across entries.
+    fn send_sequence(&mut self, seq: &[(u8, &[u8])]) -> Result<(), Self::Error> {
+        for &(cmd, data) in seq {
+            if data.is_empty() {
+                self.send_command(cmd)?;
+            } else {
+                self.send_command_with_data(cmd, data)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends a full frame from a `'static` framebuffer.
+    ///
+    /// Used by `flush` for drivers built with a static framebuffer. The
+    /// buffer is lent by value so DMA-capable implementations can hand it
+    /// straight to a transfer instead of copying it into DMA memory; they
+    /// must wait for the transfer and return the same buffer alongside the
+    /// result. Defaults to [`Self::send_pixels`].
+    fn send_frame_static(
+        &mut self,
+        frame: &'static mut [u8],
+    ) -> (&'static mut [u8], Result<(), Self::Error>) {
+        let result = self.send_pixels(frame);
+        (frame, result)
+    }
+
     /// Starts a pixel write transaction for an already configured display window.
     fn send_pixels_start(&mut self, pixels: &[u8]) -> Result<(), Self::Error> {
         self.send_pixels(pixels)
@@ -102,6 +510,52 @@ pub trait ControllerInterface {
     fn send_pixels_continue(&mut self, pixels: &[u8]) -> Result<(), Self::Error> {
         self.send_pixels(pixels)
     }
+
+    /// Starts sending pixel data for a double-buffered flush.
+    ///
+    /// Implementations may return before the transfer has finished, letting
+    /// the application draw while the bus is busy, but must not keep a
+    /// reference to `pixels` after returning (copy the tail into an owned DMA
+    /// buffer instead). Completion is reported by [`Self::is_transfer_complete`].
+    /// Defaults to the blocking [`Self::send_pixels`]; `Lgt4s3Driver` leaves
+    /// its last DMA chunk running.
+    fn send_pixels_deferred(&mut self, pixels: &[u8]) -> Result<(), Self::Error> {
+        self.send_pixels(pixels)
+    }
+
+    /// Returns `true` once the last deferred pixel transfer has finished.
+    ///
+    /// Defaults to `true`, matching the blocking default of [`Self::send_pixels_deferred`].
+    fn is_transfer_complete(&mut self) -> bool {
+        true
+    }
+
+    /// Blocks until the last deferred pixel transfer has finished.
+    fn wait_transfer_complete(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Returns `true` if pixel data can be sent over four data lanes.
+    ///
+    /// Defaults to `false`. Interfaces wired for QSPI should override this so
+    /// applications can pick the matching pixel path at runtime.
+    fn supports_quad(&self) -> bool {
+        false
+    }
+
+    /// Color modes the panel behind this interface is wired for.
+    ///
+    /// Defaults to every [`ColorMode`]. Modules that lack, for example, RGB666
+    /// support should override this so the driver rejects that mode at construction.
+    fn supported_color_modes(&self) -> &'static [ColorMode] {
+        ALL_COLOR_MODES
+    }
+}
+
+/// Trait for controller interfaces that can read data back from the display.
+pub trait ControllerReadInterface: ControllerInterface {
+    /// Sends a read command and fills `buf` with the bytes returned by the display.
+    fn read_command(&mut self, cmd: u8, buf: &mut [u8]) -> Result<(), Self::Error>;
 }
 
 /// Trait for controlling the hardware reset pin.
@@ -111,6 +565,37 @@ pub trait ResetInterface {
 
     /// Performs the hardware reset sequence according to the datasheet definition.
     fn reset(&mut self) -> Result<(), Self::Error>;
+
+    /// Switches off the panel's power rail, if this reset controls one.
+    ///
+    /// Called by `Rm690b0Driver::shutdown`. Defaults to doing nothing.
+    fn power_off(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Trait for switching the panel's power rail.
+///
+/// Combine it with a reset through [`PoweredReset`] to let the driver run
+/// the power-up and shutdown sequences.
+pub trait PowerInterface {
+    /// The specific error type for this power implementation.
+    type Error;
+
+    /// Switches the power rail on.
+    fn power_on(&mut self) -> Result<(), Self::Error>;
+
+    /// Switches the power rail off.
+    fn power_off(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Trait for waiting on the panel's tearing effect (TE) output.
+///
+/// Implement it with a GPIO input (busy-polling for the edge) or an
+/// interrupt-driven signal, whichever the board provides.
+pub trait TearingEffectPin {
+    /// Blocks until the next TE pulse, i.e. the start of the vertical blanking period.
+    fn wait_for_vsync(&mut self);
 }
 
 /// RM690B0 Command Set
@@ -139,9 +624,11 @@ pub mod commands {
     pub const RASET: u8 = 0x2B; // Row Address Set
     pub const RAMWR: u8 = 0x2C; // Memory Write
     pub const PTLAR: u8 = 0x30; // Partial Area
+    pub const VSCRDEF: u8 = 0x33; // Vertical Scrolling Definition
     pub const TEOFF: u8 = 0x34; // Tearing Effect Off
     pub const TEON: u8 = 0x35; // Tearing Effect On
     pub const MADCTR: u8 = 0x36; // Memory Data Access Control
+    pub const VSCRSADD: u8 = 0x37; // Vertical Scrolling Start Address
     pub const IDMOFF: u8 = 0x38; // Idle Mode Off
     pub const IDMON: u8 = 0x39; // Idle Mode On
     pub const COLMOD: u8 = 0x3A; // Interface Pixel Format
@@ -165,10 +652,250 @@ pub mod commands {
     pub const RDDDBC: u8 = 0xA8; // Read DDB Continuous
     pub const RDFCS: u8 = 0xAA; // Read First Checksum
     pub const RDCCS: u8 = 0xAF; // Read Continue Checksum
+    pub const CMD_PAGE: u8 = 0xFE; // Command Page Select (0x00 = user command set)
+}
+
+/// A sequence of `(command, data)` register writes.
+pub type InitSequence = &'static [(u8, &'static [u8])];
+
+/// Manufacturer-specific register writes for the Lilygo T4-S3 panel,
+/// sent after SLPOUT and MADCTR during initialization.
+///
+/// Used when no other sequence is given to [`Rm690b0Builder::init_sequence`].
+pub const LILYGO_T4_S3_INIT: InitSequence = &[
+    (0xFE, &[0x20]),
+    (0x26, &[0x0A]),
+    (0x24, &[0x80]),
+    (0x5A, &[0x51]),
+    (0x5B, &[0x2E]),
+    (0xFE, &[0x00]),
+];
+
+/// Number of coefficients per polarity in a [`GammaCurve`].
+pub const GAMMA_POINTS: usize = 16;
+
+/// Positive and negative gamma coefficients, uploaded with [`Rm690b0Driver::set_gamma`].
+///
+/// Gamma registers live on a manufacturer command page, and the page and
+/// register addresses differ between panel vendors, so they are part of the
+/// curve. Check them against the panel's datasheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GammaCurve {
+    /// Command page (CMD_PAGE value) holding the gamma registers.
+    pub page: u8,
+    /// Register of the first positive gamma coefficient; the rest follow consecutively.
+    pub positive_register: u8,
+    /// Register of the first negative gamma coefficient; the rest follow consecutively.
+    pub negative_register: u8,
+    /// Positive gamma coefficients, darkest to brightest.
+    pub positive: [u8; GAMMA_POINTS],
+    /// Negative gamma coefficients, darkest to brightest.
+    pub negative: [u8; GAMMA_POINTS],
+}
+
+impl GammaCurve {
+    /// A 2.2 power curve, close to sRGB, using page 0x50 with positive
+    /// coefficients from register 0x00 and negative ones from 0x20.
+    ///
+    /// Both polarities get the same curve, `255 * (i / 15)^(1 / 2.2)`. Check
+    /// the page and registers against the panel's datasheet before use.
+    pub const fn srgb_default() -> Self {
+        const CURVE: [u8; GAMMA_POINTS] = [
+            0, 74, 102, 123, 140, 155, 168, 180, 192, 202, 212, 221, 230, 239, 247, 255,
+        ];
+        GammaCurve {
+            page: 0x50,
+            positive_register: 0x00,
+            negative_register: 0x20,
+            positive: CURVE,
+            negative: CURVE,
+        }
+    }
+}
+
+/// Stages of the display initialization sequence, reported by
+/// [`Rm690b0Driver::initialize_display_with_progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InitStep {
+    /// Leaving sleep mode (SLPOUT).
+    SleepOut,
+    /// Writing the default memory access control (MADCTR).
+    MemoryAccessControl,
+    /// Writing a manufacturer-specific register, identified by its command byte.
+    ManufacturerRegister(u8),
+    /// Setting the interface pixel format (COLMOD).
+    PixelFormat,
+    /// Enabling (TEON) or disabling (TEOFF) the tearing effect output.
+    TearingEffect,
+    /// Enabling brightness control (WRCTRLD).
+    DisplayControl,
+    /// Writing the brightness (WRDISBV).
+    Brightness,
+    /// Turning the panel on (DISPON).
+    DisplayOn,
+}
+
+impl InitStep {
+    /// Returns the command byte sent during this step.
+    ///
+    /// [`InitStep::TearingEffect`] reports TEON, even when the driver is
+    /// configured to leave the output disabled and sends TEOFF.
+    pub const fn command(&self) -> u8 {
+        match self {
+            InitStep::SleepOut => commands::SLPOUT,
+            InitStep::MemoryAccessControl => commands::MADCTR,
+            InitStep::ManufacturerRegister(cmd) => *cmd,
+            InitStep::PixelFormat => commands::COLMOD,
+            InitStep::TearingEffect => commands::TEON,
+            InitStep::DisplayControl => commands::WRCTRLD,
+            InitStep::Brightness => commands::WRDISBV,
+            InitStep::DisplayOn => commands::DISPON,
+        }
+    }
+}
+
+/// Display orientation, applied through the MADCTR register.
+///
+/// Landscape variants set the MV bit, which swaps row and column addressing
+/// in the controller, so the logical width and height are swapped as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Native panel orientation.
+    Portrait,
+    /// Rotated 90 degrees clockwise.
+    Landscape,
+    /// Rotated 180 degrees.
+    PortraitFlipped,
+    /// Rotated 270 degrees clockwise.
+    LandscapeFlipped,
+}
+
+impl Orientation {
+    /// Returns the MADCTR value (MY/MX/MV bits) for this orientation.
+    pub const fn madctr(&self) -> u8 {
+        match self {
+            Orientation::Portrait => 0x00,
+            Orientation::Landscape => madctr::MV | madctr::MX,
+            Orientation::PortraitFlipped => madctr::MY | madctr::MX,
+            Orientation::LandscapeFlipped => madctr::MV | madctr::MY,
+        }
+    }
+
+    /// Returns `true` if width and height are swapped relative to the panel.
+    pub const fn is_landscape(&self) -> bool {
+        matches!(self, Orientation::Landscape | Orientation::LandscapeFlipped)
+    }
+}
+
+/// Rotation applied in software while drawing into the framebuffer.
+///
+/// Unlike [`Orientation`], MADCTR is left untouched: drawing coordinates are
+/// mapped onto the framebuffer's existing layout, which `flush` sends as-is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SoftwareRotation {
+    /// No rotation.
+    #[default]
+    None,
+    /// Rotated 90 degrees clockwise.
+    Rotate90,
+    /// Rotated 180 degrees.
+    Rotate180,
+    /// Rotated 270 degrees clockwise.
+    Rotate270,
+}
+
+impl SoftwareRotation {
+    /// Returns `true` if width and height are swapped relative to the framebuffer.
+    pub const fn swaps_axes(&self) -> bool {
+        matches!(
+            self,
+            SoftwareRotation::Rotate90 | SoftwareRotation::Rotate270
+        )
+    }
+}
+
+/// Order of the color channels within a pixel sent to the panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorOrder {
+    /// Red first.
+    #[default]
+    Rgb,
+    /// Blue first.
+    Bgr,
+}
+
+/// Byte order of 16-bit pixels on the interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Endianness {
+    /// Most significant byte first.
+    #[default]
+    Big,
+    /// Least significant byte first.
+    Little,
+}
+
+/// Interface pixel format options, applied with `set_pixel_format_option`.
+///
+/// The byte order of 16-bit pixels is not covered: the COLOPT bit layout is
+/// not part of the public RM690B0 command description. Use
+/// [`Rm690b0Driver::set_rgb565_endianness`] to change the byte order
+/// drawing stores instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PixelFormatOptions {
+    /// Channel order, applied through the MADCTR RGB bit.
+    pub color_order: ColorOrder,
+}
+
+impl PixelFormatOptions {
+    /// Returns the MADCTR bits contributed by these options.
+    pub const fn madctr_bits(&self) -> u8 {
+        match self.color_order {
+            ColorOrder::Rgb => 0x00,
+            ColorOrder::Bgr => madctr::RGB,
+        }
+    }
+}
+
+/// When the tearing effect (TE) output pulses, selected by the TEON parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TearMode {
+    /// Pulse on vertical blanking only.
+    VBlankOnly,
+    /// Pulse on both vertical and horizontal blanking.
+    VBlankAndHBlank,
+}
+
+impl TearMode {
+    /// Returns the TEON parameter selecting this mode.
+    pub const fn teon(&self) -> u8 {
+        match self {
+            TearMode::VBlankOnly => 0x00,
+            TearMode::VBlankAndHBlank => 0x01,
+        }
+    }
+
+    /// Returns the command and data that apply `mode`, with `None` meaning TEOFF.
+    const fn command(mode: Option<TearMode>) -> (u8, &'static [u8]) {
+        match mode {
+            Some(TearMode::VBlankOnly) => (commands::TEON, &[0x00]),
+            Some(TearMode::VBlankAndHBlank) => (commands::TEON, &[0x01]),
+            None => (commands::TEOFF, &[]),
+        }
+    }
+}
+
+/// Memory Data Access Control (MADCTR) register bits.
+pub mod madctr {
+    pub const MY: u8 = 0x80; // Row Address Order
+    pub const MX: u8 = 0x40; // Column Address Order
+    pub const MV: u8 = 0x20; // Row/Column Exchange
+    pub const ML: u8 = 0x10; // Vertical Refresh Order
+    pub const RGB: u8 = 0x08; // RGB/BGR Order
 }
 
 /// Color modes supported by the RM690B0 display controller.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ColorMode {
     /// 16-bit RGB565 format
     Rgb565,
@@ -181,6 +908,27 @@ pub enum ColorMode {
 }
 
 impl ColorMode {
+    /// Returns the COLMOD parameter selecting this interface pixel format.
+    pub const fn colmod(&self) -> u8 {
+        match self {
+            ColorMode::Rgb565 => 0x55,
+            ColorMode::Rgb888 => 0x77,
+            ColorMode::Rgb666 => 0x66,
+            ColorMode::Gray8 => 0x11,
+        }
+    }
+
+    /// Returns the color mode selected by a COLMOD parameter, if it is one of the supported formats.
+    pub const fn from_colmod(value: u8) -> Option<Self> {
+        match value {
+            0x55 => Some(ColorMode::Rgb565),
+            0x77 => Some(ColorMode::Rgb888),
+            0x66 => Some(ColorMode::Rgb666),
+            0x11 => Some(ColorMode::Gray8),
+            _ => None,
+        }
+    }
+
     /// Returns the number of bytes per pixel for the color format.
     pub const fn bytes_per_pixel(&self) -> usize {
         match self {
@@ -192,62 +940,473 @@ impl ColorMode {
     }
 }
 
-/// Computes the framebuffer size (in bytes) for a given display and color mode.
-pub const fn framebuffer_size(display: DisplaySize, color: ColorMode) -> usize {
-    (display.width as usize) * (display.height as usize) * color.bytes_per_pixel()
+/// Pixel color types with a native RM690B0 wire format.
+///
+/// Ties a color type to the [`ColorMode`] that stores it without
+/// conversion, so the color mode and framebuffer stride follow from the
+/// driver's color type. Use it with [`Rm690b0Builder::native`] and
+/// [`framebuffer_size_of`].
+pub trait WireFormat: PixelColor {
+    /// The color mode storing this color type.
+    const COLOR_MODE: ColorMode;
+    /// Framebuffer bytes per pixel.
+    const BYTES_PER_PIXEL: usize = Self::COLOR_MODE.bytes_per_pixel();
 }
 
-/// Framebuffer enum to hold either a static array or a boxed array
-pub enum Framebuffer {
-    Static(&'static mut [u8]),
-    Heap(Box<[u8]>),
+impl WireFormat for Rgb565 {
+    const COLOR_MODE: ColorMode = ColorMode::Rgb565;
 }
 
-impl Framebuffer {
-    /// Returns a mutable view of the raw framebuffer bytes.
-    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+impl WireFormat for Rgb888 {
+    const COLOR_MODE: ColorMode = ColorMode::Rgb888;
+}
+
+impl WireFormat for Gray8 {
+    const COLOR_MODE: ColorMode = ColorMode::Gray8;
+}
+
+/// Panel refresh rates selectable with FR_LEVEL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameRate {
+    /// 30 Hz, for static content.
+    Hz30,
+    /// 60 Hz, the power-on default.
+    Hz60,
+    /// 90 Hz. Only available with 16-bit and 8-bit pixel formats
+    /// ([`ColorMode::Rgb565`] and [`ColorMode::Gray8`]).
+    Hz90,
+}
+
+impl FrameRate {
+    /// Returns the FR_LEVEL parameter for this rate.
+    pub const fn fr_level(&self) -> u8 {
         match self {
-            Framebuffer::Static(ref mut arr) => arr,
-            Framebuffer::Heap(ref mut boxed) => boxed,
+            FrameRate::Hz30 => 0x00,
+            FrameRate::Hz60 => 0x01,
+            FrameRate::Hz90 => 0x02,
         }
     }
 
-    /// Returns an immutable view of the raw framebuffer bytes.
-    pub fn as_slice(&self) -> &[u8] {
+    /// Returns the rate for an FR_LEVEL parameter, or `None` if it is not one of these rates.
+    pub const fn from_fr_level(level: u8) -> Option<Self> {
+        match level {
+            0x00 => Some(FrameRate::Hz30),
+            0x01 => Some(FrameRate::Hz60),
+            0x02 => Some(FrameRate::Hz90),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the panel can refresh at this rate in the given color mode.
+    pub const fn supports(&self, color_mode: ColorMode) -> bool {
         match self {
-            Framebuffer::Static(ref arr) => arr,
-            Framebuffer::Heap(ref boxed) => boxed,
+            FrameRate::Hz90 => matches!(color_mode, ColorMode::Rgb565 | ColorMode::Gray8),
+            _ => true,
         }
     }
+}
 
-    /// Returns the framebuffer length in bytes.
-    pub fn len(&self) -> usize {
-        self.as_slice().len()
+/// Whether the panel is showing an image, as tracked by the driver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PanelState {
+    /// The display is on (DISPON).
+    pub on: bool,
+    /// The panel is in sleep mode (SLPIN) or deep standby.
+    pub sleeping: bool,
+}
+
+impl PanelState {
+    /// State after a reset: asleep with the display off.
+    const RESET: PanelState = PanelState {
+        on: false,
+        sleeping: true,
+    };
+}
+
+/// Display control bits written with WRCTRLD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayControl {
+    /// Brightness control block on (BCTRL); without it WRDISBV may be ignored.
+    pub brightness_control: bool,
+    /// Display dimming on (DD), smoothing brightness changes.
+    pub dimming: bool,
+    /// Backlight on (BL).
+    pub backlight: bool,
+}
+
+impl DisplayControl {
+    /// Returns the WRCTRLD parameter for these bits.
+    pub const fn bits(&self) -> u8 {
+        (self.brightness_control as u8) << 5
+            | (self.dimming as u8) << 3
+            | (self.backlight as u8) << 2
     }
 }
 
-impl core::ops::Deref for Framebuffer {
-    type Target = [u8];
-    fn deref(&self) -> &Self::Target {
+impl Default for DisplayControl {
+    /// Brightness control on, dimming and backlight off: the value written during initialization.
+    fn default() -> Self {
+        DisplayControl {
+            brightness_control: true,
+            dimming: false,
+            backlight: false,
+        }
+    }
+}
+
+/// Color temperature presets, written to bits 5:4 of register 0x55 (COLORTEMP).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorTemp {
+    /// Native panel white point.
+    Standard,
+    /// Warmer white point, e.g. for a night mode.
+    Warm,
+    /// Cooler white point.
+    Cool,
+}
+
+impl ColorTemp {
+    /// Bits of register 0x55 holding the color temperature.
+    pub const MASK: u8 = 0x30;
+
+    /// Returns the bits this preset sets in register 0x55.
+    pub const fn bits(&self) -> u8 {
         match self {
-            Framebuffer::Static(arr) => arr,
-            Framebuffer::Heap(boxed) => boxed,
+            ColorTemp::Standard => 0x00,
+            ColorTemp::Warm => 0x10,
+            ColorTemp::Cool => 0x20,
         }
     }
 }
 
-impl core::ops::DerefMut for Framebuffer {
-    fn deref_mut(&mut self) -> &mut Self::Target {
+/// Automatic current limiting levels, written to bits 1:0 of register 0x55 (WRRADACL).
+///
+/// Higher levels cap the panel current harder on bright, mostly-white
+/// content, reducing power and burn-in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AclLevel {
+    /// Current limiting disabled.
+    Off,
+    /// Light limiting.
+    Low,
+    /// Medium limiting.
+    Medium,
+    /// Strong limiting.
+    High,
+}
+
+impl AclLevel {
+    /// Bits of register 0x55 holding the current limiting level.
+    pub const MASK: u8 = 0x03;
+
+    /// Returns the bits this level sets in register 0x55.
+    pub const fn bits(&self) -> u8 {
         match self {
-            Framebuffer::Static(arr) => arr,
-            Framebuffer::Heap(boxed) => boxed,
+            AclLevel::Off => 0x00,
+            AclLevel::Low => 0x01,
+            AclLevel::Medium => 0x02,
+            AclLevel::High => 0x03,
         }
     }
 }
 
-/// Main Driver for the RM690B0 display controller.
-pub struct Rm690b0Driver<IFACE, RST, C>
-where
+/// Display power mode as reported by RDDPM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerMode {
+    /// Booster voltage is on.
+    pub booster_on: bool,
+    /// Idle mode is on.
+    pub idle_mode: bool,
+    /// Partial display mode is on.
+    pub partial_mode: bool,
+    /// The panel is out of sleep mode.
+    pub sleep_out: bool,
+    /// Normal display mode is on.
+    pub normal_mode: bool,
+    /// The display is on.
+    pub display_on: bool,
+}
+
+impl PowerMode {
+    /// Decodes the RDDPM status byte.
+    pub const fn from_bits(bits: u8) -> Self {
+        PowerMode {
+            booster_on: bits & 0x80 != 0,
+            idle_mode: bits & 0x40 != 0,
+            partial_mode: bits & 0x20 != 0,
+            sleep_out: bits & 0x10 != 0,
+            normal_mode: bits & 0x08 != 0,
+            display_on: bits & 0x04 != 0,
+        }
+    }
+}
+
+/// What a `present` call sent to the panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Pixel bytes transferred, including the padding to even boundaries.
+    pub bytes_sent: usize,
+    /// Region drawn during the frame, in framebuffer coordinates, or `None` if nothing was drawn.
+    pub dirty_area: Option<Rectangle>,
+}
+
+/// Bring-up test images drawn by `draw_test_pattern`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestPattern {
+    /// Eight vertical bars: white, yellow, cyan, green, magenta, red, blue and black.
+    ColorBars,
+    /// Alternating white and black 16x16 squares.
+    Checkerboard,
+    /// Red increasing to the right and green increasing downwards.
+    Gradient,
+    /// The whole screen in one color.
+    SolidColor(Rgb888),
+}
+
+impl TestPattern {
+    /// Returns the color of pixel `(x, y)` on a `width` x `height` screen.
+    fn color_at(&self, x: u16, y: u16, width: u16, height: u16) -> Rgb888 {
+        const BARS: [Rgb888; 8] = [
+            Rgb888::new(0xFF, 0xFF, 0xFF),
+            Rgb888::new(0xFF, 0xFF, 0x00),
+            Rgb888::new(0x00, 0xFF, 0xFF),
+            Rgb888::new(0x00, 0xFF, 0x00),
+            Rgb888::new(0xFF, 0x00, 0xFF),
+            Rgb888::new(0xFF, 0x00, 0x00),
+            Rgb888::new(0x00, 0x00, 0xFF),
+            Rgb888::new(0x00, 0x00, 0x00),
+        ];
+        match self {
+            TestPattern::ColorBars => BARS[x as usize * BARS.len() / width as usize],
+            TestPattern::Checkerboard => {
+                if (x / 16 + y / 16) % 2 == 0 {
+                    Rgb888::new(0xFF, 0xFF, 0xFF)
+                } else {
+                    Rgb888::new(0x00, 0x00, 0x00)
+                }
+            }
+            TestPattern::Gradient => Rgb888::new(
+                (x as u32 * 0xFF / (width as u32 - 1).max(1)) as u8,
+                (y as u32 * 0xFF / (height as u32 - 1).max(1)) as u8,
+                0x00,
+            ),
+            TestPattern::SolidColor(color) => *color,
+        }
+    }
+}
+
+/// Display identification as reported by RDDID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DisplayInfo {
+    /// Module manufacturer ID.
+    pub manufacturer_id: u8,
+    /// Module/driver version ID, useful to tell panel revisions apart.
+    pub version: u8,
+    /// Module/driver ID.
+    pub driver_id: u8,
+}
+
+impl DisplayInfo {
+    /// Decodes the three RDDID bytes.
+    pub const fn from_bytes(bytes: [u8; 3]) -> Self {
+        DisplayInfo {
+            manufacturer_id: bytes[0],
+            version: bytes[1],
+            driver_id: bytes[2],
+        }
+    }
+}
+
+/// Computes the framebuffer size (in bytes) for a given display and color mode.
+///
+/// Overflows on targets with a 16-bit `usize` for all but tiny panels,
+/// panicking in debug builds (and failing compilation in const contexts).
+/// Use [`checked_framebuffer_size`] where that matters.
+pub const fn framebuffer_size(display: DisplaySize, color: ColorMode) -> usize {
+    (display.width as usize) * (display.height as usize) * color.bytes_per_pixel()
+}
+
+/// Creates a `'static` framebuffer sized for a display size and color mode at compile time.
+///
+/// Expands to an `Option<&'static mut [u8; N]>`, with `N` computed by
+/// [`framebuffer_size`], so the buffer passed to `new_static` or
+/// `Rm690b0Builder::build_static` always has the right length. Both
+/// arguments must be constant expressions. Each expansion owns one buffer and
+/// returns it only the first time it runs, and `None` afterwards. Needs
+/// atomic compare-and-swap support on the target.
+///
+/// ```
+/// use rm690b0_rs::{framebuffer_size, rm690b0_framebuffer, ColorMode, DisplaySize};
+///
+/// const SIZE: DisplaySize = DisplaySize::new(450, 600);
+/// let framebuffer = rm690b0_framebuffer!(SIZE, ColorMode::Rgb565).unwrap();
+/// assert_eq!(framebuffer.len(), framebuffer_size(SIZE, ColorMode::Rgb565));
+/// ```
+#[macro_export]
+macro_rules! rm690b0_framebuffer {
+    ($size:expr, $color:expr) => {{
+        const LEN: usize = $crate::framebuffer_size($size, $color);
+        static TAKEN: ::core::sync::atomic::AtomicBool =
+            ::core::sync::atomic::AtomicBool::new(false);
+        static mut FRAMEBUFFER: [u8; LEN] = [0; LEN];
+        if TAKEN.swap(true, ::core::sync::atomic::Ordering::AcqRel) {
+            None
+        } else {
+            // SAFETY: `TAKEN` ensures this reference is only ever created once.
+            Some(unsafe { &mut *::core::ptr::addr_of_mut!(FRAMEBUFFER) })
+        }
+    }};
+}
+
+/// Computes the framebuffer size (in bytes) for a display drawn with color type `C`.
+pub const fn framebuffer_size_of<C: WireFormat>(display: DisplaySize) -> usize {
+    framebuffer_size(display, C::COLOR_MODE)
+}
+
+/// Computes the framebuffer size (in bytes), or `None` if it overflows `usize`.
+pub const fn checked_framebuffer_size(display: DisplaySize, color: ColorMode) -> Option<usize> {
+    match (display.width as usize).checked_mul(display.height as usize) {
+        Some(pixels) => pixels.checked_mul(color.bytes_per_pixel()),
+        None => None,
+    }
+}
+
+/// Returns the smallest rectangle enclosing every pixel that differs between `a` and `b`.
+///
+/// Both slices are interpreted as framebuffers `width` pixels wide with `bpp`
+/// bytes per pixel; only their common length is compared. Returns `None` when
+/// the frames are identical.
+pub fn dirty_bounds(a: &[u8], b: &[u8], width: u16, bpp: usize) -> Option<Rectangle> {
+    let stride = width as usize * bpp;
+    if stride == 0 {
+        return None;
+    }
+
+    let rows = a.len().min(b.len()) / stride;
+    let mut min_x = usize::MAX;
+    let mut max_x = 0;
+    let mut min_y = usize::MAX;
+    let mut max_y = 0;
+
+    for y in 0..rows {
+        let row_a = &a[y * stride..(y + 1) * stride];
+        let row_b = &b[y * stride..(y + 1) * stride];
+        if row_a == row_b {
+            continue;
+        }
+        for x in 0..width as usize {
+            let pixel = x * bpp..(x + 1) * bpp;
+            if row_a[pixel.clone()] != row_b[pixel] {
+                min_x = min_x.min(x);
+                max_x = max_x.max(x);
+            }
+        }
+        min_y = min_y.min(y);
+        max_y = y;
+    }
+
+    if min_y == usize::MAX {
+        return None;
+    }
+    Some(Rectangle::new(
+        Point::new(min_x as i32, min_y as i32),
+        Size::new((max_x - min_x + 1) as u32, (max_y - min_y + 1) as u32),
+    ))
+}
+
+/// Framebuffer enum to hold either a static array or a boxed array
+pub enum Framebuffer {
+    Static(&'static mut [u8]),
+    #[cfg(feature = "alloc")]
+    Heap(Box<[u8]>),
+}
+
+impl Framebuffer {
+    /// Returns a mutable view of the raw framebuffer bytes.
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        match self {
+            Framebuffer::Static(ref mut arr) => arr,
+            #[cfg(feature = "alloc")]
+            Framebuffer::Heap(ref mut boxed) => boxed,
+        }
+    }
+
+    /// Returns an immutable view of the raw framebuffer bytes.
+    pub fn as_slice(&self) -> &[u8] {
+        match self {
+            Framebuffer::Static(ref arr) => arr,
+            #[cfg(feature = "alloc")]
+            Framebuffer::Heap(ref boxed) => boxed,
+        }
+    }
+
+    /// Returns the framebuffer length in bytes.
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    /// Returns `true` if the framebuffer holds no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    /// Returns `true` if the framebuffer can be resized to `len` bytes.
+    ///
+    /// Heap buffers can take any size; static buffers can only shrink.
+    fn can_resize(&self, len: usize) -> bool {
+        match self {
+            Framebuffer::Static(arr) => len <= arr.len(),
+            #[cfg(feature = "alloc")]
+            Framebuffer::Heap(_) => true,
+        }
+    }
+
+    /// Resizes the framebuffer to `len` bytes, checked with [`Self::can_resize`].
+    ///
+    /// Heap buffers are reallocated and zeroed; static buffers are
+    /// truncated, giving up their tail for good.
+    fn resize(&mut self, len: usize) {
+        if len == self.len() {
+            return;
+        }
+        match self {
+            Framebuffer::Static(arr) => {
+                let full = core::mem::take(arr);
+                *arr = &mut full[..len];
+            }
+            #[cfg(feature = "alloc")]
+            Framebuffer::Heap(boxed) => {
+                *boxed = alloc::vec![0u8; len].into_boxed_slice();
+            }
+        }
+    }
+}
+
+impl core::ops::Deref for Framebuffer {
+    type Target = [u8];
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Framebuffer::Static(arr) => arr,
+            #[cfg(feature = "alloc")]
+            Framebuffer::Heap(boxed) => boxed,
+        }
+    }
+}
+
+impl core::ops::DerefMut for Framebuffer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Framebuffer::Static(arr) => arr,
+            #[cfg(feature = "alloc")]
+            Framebuffer::Heap(boxed) => boxed,
+        }
+    }
+}
+
+/// Main Driver for the RM690B0 display controller.
+pub struct Rm690b0Driver<IFACE, RST, C>
+where
     IFACE: ControllerInterface,
     RST: ResetInterface,
     C: PixelColor,
@@ -257,6 +1416,57 @@ where
     framebuffer: Framebuffer,
     config: DisplaySize,
     color_mode: ColorMode,
+    /// Manufacturer-specific register writes sent during initialization.
+    init_sequence: InitSequence,
+    /// Tearing effect output mode set during initialization, `None` for TEOFF.
+    tear_mode: Option<TearMode>,
+    /// Minimum time between paced flushes in microseconds, 0 when unlimited.
+    min_frame_interval_us: u64,
+    /// Timestamp of the last paced flush in microseconds.
+    last_flush_us: Option<u64>,
+    /// Inclusive panel row range last written to PTLAR.
+    partial_area: Option<(u16, u16)>,
+    /// Set while the panel is in partial display mode (PTLON), limiting what
+    /// `flush` transmits to `partial_area`.
+    partial_mode: bool,
+    /// Last value written to MADCTR.
+    madctr: u8,
+    /// Offset applied to every window in display RAM, in pixels.
+    pixel_shift: (i16, i16),
+    /// Position in the orbit stepped through by `burn_in_shift`.
+    burn_in_step: u8,
+    orientation: Orientation,
+    /// Rotation applied to drawing coordinates, on top of `orientation`.
+    software_rotation: SoftwareRotation,
+    /// Last options written with `set_pixel_format_option`.
+    pixel_format: PixelFormatOptions,
+    /// Whether drawing stores colors as B, G, R in the framebuffer.
+    bgr: bool,
+    /// Byte order drawing uses for RGB565 pixels.
+    rgb565_endianness: Endianness,
+    /// Whether display inversion (INVON) is active.
+    inverted: bool,
+    /// Display on and sleep state set by the driver's commands.
+    panel_state: PanelState,
+    /// Set while the panel shows ALLPON/ALLPOFF instead of display RAM.
+    all_pixels_forced: bool,
+    /// Second framebuffer used by `flush_double_buffered`, holding the frame being sent.
+    back_buffer: Option<Framebuffer>,
+    /// Inclusive `(x_start, y_start, x_end, y_end)` bounds of pixels drawn since the last dirty flush.
+    dirty: Option<(u16, u16, u16, u16)>,
+    /// One bit per logical row drawn since the last dirty flush.
+    dirty_rows: DirtyRows,
+    /// Last value written to WRDISBV.
+    brightness: u8,
+    /// Last value written to register 0x55, shared by COLORTEMP and WRRADACL.
+    rad_acl: u8,
+    /// Last value written to FR_LEVEL, `None` while at the power-on default.
+    frame_rate_level: Option<u8>,
+    /// First row not yet sent by an interrupted `flush_resumable`.
+    resume_row: Option<u16>,
+    /// Last window set with CASET/RASET, in logical coordinates.
+    #[cfg(any(test, feature = "test-internals"))]
+    window: Option<(u16, u16, u16, u16)>,
     _color: core::marker::PhantomData<C>,
 }
 
@@ -267,71 +1477,148 @@ where
     C: PixelColor,
 {
     /// Creates a new driver instance with static array and initializes the display.
+    ///
+    /// Shorthand for [`Rm690b0Builder`] with only the color mode and size set.
     pub fn new_static<DELAY, const N: usize>(
         interface: IFACE,
         reset: RST,
         colormode: ColorMode,
         config: DisplaySize,
-        mut delay: DELAY,
+        delay: DELAY,
         framebuffer: &'static mut [u8; N],
     ) -> Result<Self, DriverError<IFACE::Error, RST::Error>>
     where
         DELAY: DelayNs,
     {
-        let expected_fb_size = framebuffer_size(config, colormode);
-        if N != expected_fb_size {
-            return Err(DriverError::InvalidConfiguration(
-                "Framebuffer size does not match display size and color mode",
-            ));
-        }
-
-        let mut driver = Self {
-            interface,
-            reset,
-            framebuffer: Framebuffer::Static(&mut framebuffer[..]),
-            config,
-            color_mode: colormode,
-            _color: core::marker::PhantomData,
-        };
-        driver.hard_reset()?;
-        driver.initialize_display(&mut delay, colormode)?;
-        Ok(driver)
+        Rm690b0Builder::new(interface, reset)
+            .color_mode(colormode)
+            .size(config)
+            .build_static(delay, framebuffer)
     }
 
     /// Creates a new driver instance with a boxed array framebuffer.
+    ///
+    /// Shorthand for [`Rm690b0Builder`] with only the color mode and size set.
+    #[cfg(feature = "alloc")]
     pub fn new_heap<DELAY, const N: usize>(
         interface: IFACE,
         reset: RST,
         color: ColorMode,
         config: DisplaySize,
-        mut delay: DELAY,
+        delay: DELAY,
     ) -> Result<Self, DriverError<IFACE::Error, RST::Error>>
     where
         DELAY: DelayNs,
     {
-        let expected_fb_size = framebuffer_size(config, color);
-        if N != expected_fb_size {
+        Rm690b0Builder::new(interface, reset)
+            .color_mode(color)
+            .size(config)
+            .build_heap::<_, N>(delay)
+    }
+
+    /// Returns the color mode the driver was configured with.
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Returns the configured panel width in pixels, regardless of orientation.
+    ///
+    /// `OriginDimensions::size` reports the drawing size instead, swapped in
+    /// landscape and by software rotation.
+    pub fn width(&self) -> u16 {
+        self.config.width
+    }
+
+    /// Returns the configured panel height in pixels, regardless of orientation.
+    pub fn height(&self) -> u16 {
+        self.config.height
+    }
+
+    /// Returns the display size and RAM offsets the driver was configured with.
+    pub fn size_config(&self) -> DisplaySize {
+        self.config
+    }
+
+    /// Switches the interface pixel format at runtime (COLMOD).
+    ///
+    /// The framebuffer is resized to match the new bytes per pixel. A heap
+    /// framebuffer is reallocated and cleared when its size changes. A
+    /// static framebuffer cannot grow: switching to a mode that needs more
+    /// bytes than the backing array returns an error, and switching to a
+    /// smaller one truncates it, so the driver cannot switch back to the
+    /// larger mode afterwards. A double-buffering back buffer follows the
+    /// same rules. Modes with the same size (RGB888 and RGB666) keep the
+    /// framebuffer bytes as they are.
+    ///
+    /// Everything is marked dirty; flush to redraw the panel.
+    pub fn set_color_mode(
+        &mut self,
+        mode: ColorMode,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if !self.interface.supported_color_modes().contains(&mode) {
+            return Err(DriverError::InvalidConfiguration(
+                "unsupported color mode for this panel",
+            ));
+        }
+        if !self.frame_rate().supports(mode) {
+            return Err(DriverError::InvalidConfiguration(
+                "Frame rate is not supported in this color mode",
+            ));
+        }
+        let len = framebuffer_size(self.config, mode);
+        let back_buffer_fits = self
+            .back_buffer
+            .as_ref()
+            .is_none_or(|buffer| buffer.can_resize(len));
+        if !self.framebuffer.can_resize(len) || !back_buffer_fits {
             return Err(DriverError::InvalidConfiguration(
-                "Framebuffer size does not match display size and color mode",
+                "Static framebuffer is too small for this color mode",
             ));
         }
 
-        let mut driver = Self {
-            interface,
-            reset,
-            framebuffer: Framebuffer::Heap(Box::new([0u8; N])),
-            config,
-            color_mode: color,
-            _color: core::marker::PhantomData,
-        };
-        driver.hard_reset()?;
-        driver.initialize_display(&mut delay, color)?;
-        Ok(driver)
+        self.send_command_with_data(commands::COLMOD, &[mode.colmod()])?;
+        self.color_mode = mode;
+        self.framebuffer.resize(len);
+        if let Some(buffer) = self.back_buffer.as_mut() {
+            buffer.resize(len);
+        }
+        self.resume_row = None;
+        self.mark_all_dirty();
+        Ok(())
+    }
+
+    /// Returns `true` if the controller interface can send pixels over four data lanes.
+    pub fn supports_quad(&self) -> bool {
+        self.interface.supports_quad()
+    }
+
+    /// Returns the framebuffer contents.
+    pub fn framebuffer(&self) -> &[u8] {
+        &self.framebuffer
+    }
+
+    /// Returns the framebuffer for writing pixel data directly, bypassing `embedded-graphics`.
+    ///
+    /// The caller must write pixels in the byte layout of the driver's
+    /// [`ColorMode`], row-major with [`Self::stride`] bytes per row. Writes
+    /// are not tracked as dirty; call [`Self::mark_all_dirty`] before
+    /// [`Self::flush_dirty`], or use [`Self::flush`].
+    pub fn framebuffer_mut(&mut self) -> &mut [u8] {
+        &mut self.framebuffer
+    }
+
+    /// Returns the number of framebuffer bytes per row: the logical width times the bytes per pixel.
+    pub fn stride(&self) -> usize {
+        self.dimensions().0 as usize * self.color_mode.bytes_per_pixel()
     }
 
     /// Performs a hardware reset using the provided `ResetPin` implementation.
+    ///
+    /// The cached panel register state returns to its reset defaults, as
+    /// after [`Self::soft_reset`].
     pub fn hard_reset(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
         self.reset.reset().map_err(DriverError::ResetError)?;
+        self.forget_panel_state();
         Ok(())
     }
 
@@ -339,7 +1626,6 @@ where
     pub fn initialize_display<DELAY>(
         &mut self,
         delay: &mut DELAY,
-        color: ColorMode,
     ) -> Result<(), DriverError<IFACE::Error, RST::Error>>
     where
         DELAY: DelayNs,
@@ -347,44 +1633,70 @@ where
         self.send_command(commands::SLPOUT)?;
         delay.delay_ms(120);
 
-        self.send_command_with_data(commands::MADCTR, &[0x00])?;
+        let madctr = self.orientation_madctr();
+        InitCommands::new(madctr, self.color_mode, self.tear_mode, self.brightness)
+            .send(&mut self.interface, self.init_sequence)
+            .map_err(DriverError::InterfaceError)?;
+        self.madctr = madctr;
+        self.panel_state = PanelState {
+            on: true,
+            sleeping: false,
+        };
+        delay.delay_ms(20);
+
+        Ok(())
+    }
+
+    /// Runs the initialization sequence, calling `on_step` before each stage.
+    ///
+    /// Useful during board bring-up: logging each [`InitStep`] shows which
+    /// command the panel stalled on. Unlike [`Self::initialize_display`],
+    /// which batches the commands between SLPOUT and DISPON through
+    /// [`ControllerInterface::send_sequence`], each command is sent on its own.
+    pub fn initialize_display_with_progress<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+        mut on_step: impl FnMut(InitStep),
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>>
+    where
+        DELAY: DelayNs,
+    {
+        on_step(InitStep::SleepOut);
+        self.send_command(commands::SLPOUT)?;
+        self.panel_state.sleeping = false;
+        delay.delay_ms(120);
+
+        on_step(InitStep::MemoryAccessControl);
+        self.set_madctr(self.orientation_madctr())?;
 
         // Manufacturer-Specific Initialization
-        self.send_command_with_data(0xFE, &[0x20])?;
-        self.send_command_with_data(0x26, &[0x0A])?;
-        self.send_command_with_data(0x24, &[0x80])?;
-        self.send_command_with_data(0x5A, &[0x51])?;
-        self.send_command_with_data(0x5B, &[0x2E])?;
-        self.send_command_with_data(0xFE, &[0x00])?;
-
-        // Sets Interface Pixel Format to 24-bit/pixel (RGB888)
-        match color {
-            ColorMode::Rgb565 => {
-                // Set pixel format to RGB565
-                self.send_command_with_data(commands::COLMOD, &[0x55])?;
-            }
-            ColorMode::Rgb888 => {
-                // Set pixel format to RGB888
-                self.send_command_with_data(commands::COLMOD, &[0x77])?;
-            }
-            ColorMode::Rgb666 => {
-                // Set pixel format to RGB666
-                self.send_command_with_data(commands::COLMOD, &[0x66])?;
-            }
-            ColorMode::Gray8 => {
-                // Set pixel format to 8-bit grayscale
-                self.send_command_with_data(commands::COLMOD, &[0x11])?;
-            }
+        for &(cmd, data) in self.init_sequence {
+            on_step(InitStep::ManufacturerRegister(cmd));
+            self.send_command_with_data(cmd, data)?;
+        }
+
+        on_step(InitStep::PixelFormat);
+        self.send_command_with_data(commands::COLMOD, &[self.color_mode.colmod()])?;
+
+        on_step(InitStep::TearingEffect);
+        let (cmd, data) = TearMode::command(self.tear_mode);
+        if data.is_empty() {
+            self.send_command(cmd)?;
+        } else {
+            self.send_command_with_data(cmd, data)?;
         }
 
-        self.send_command_with_data(commands::TEON, &[0x00])?;
+        on_step(InitStep::DisplayControl);
+        self.set_display_control(DisplayControl::default())?;
 
+        on_step(InitStep::Brightness);
+        self.set_brightness(self.brightness)?;
+
+        on_step(InitStep::DisplayOn);
         self.send_command(commands::DISPON)?;
+        self.panel_state.on = true;
         delay.delay_ms(20);
 
-        // Display Brightness Set to Maximum
-        self.send_command_with_data(commands::WRDISBV, &[0xFF])?;
-
         Ok(())
     }
 
@@ -407,6 +1719,38 @@ where
         Ok(())
     }
 
+    /// Sends NOP, a cheap check that the bus link is healthy.
+    ///
+    /// NOP has no effect on the panel, so this can run on a timer next to
+    /// `read_error_count` without disturbing the image.
+    pub fn ping(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command(commands::NOP)
+    }
+
+    /// Sends `cmd` followed by `data`, bypassing the typed API.
+    ///
+    /// An escape hatch for registers the driver doesn't cover yet. The
+    /// driver's cached state (orientation, brightness, inversion, ...) is not
+    /// updated, so writing registers it tracks can leave it out of sync with
+    /// the panel.
+    pub fn write_command(
+        &mut self,
+        cmd: u8,
+        data: &[u8],
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command_with_data(cmd, data)
+    }
+
+    /// Sends `cmd` without data, bypassing the typed API.
+    ///
+    /// See [`Self::write_command`] for the caveats.
+    pub fn write_command_raw(
+        &mut self,
+        cmd: u8,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command(cmd)
+    }
+
     /// Sleep Mode In (SLPIN)
     pub fn sleep_in<DELAY>(
         &mut self,
@@ -416,6 +1760,7 @@ where
         DELAY: DelayNs,
     {
         self.send_command(commands::SLPIN)?;
+        self.panel_state.sleeping = true;
         delay.delay_ms(5);
         Ok(())
     }
@@ -429,102 +1774,1559 @@ where
         DELAY: DelayNs,
     {
         self.send_command(commands::SLPOUT)?;
+        self.panel_state.sleeping = false;
         delay.delay_ms(5);
         Ok(())
     }
 
+    /// Turns the panel off, puts it to sleep and switches off its power rail.
+    ///
+    /// The reverse of power-up: DISPOFF, SLPIN and a 120ms wait for the
+    /// panel to finish its sleep-in sequence, then
+    /// [`ResetInterface::power_off`], which cuts the rail when the reset is a
+    /// [`PoweredReset`]. A later [`Self::hard_reset`] powers the panel up
+    /// again; follow it with [`Self::initialize_display`].
+    pub fn shutdown<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>>
+    where
+        DELAY: DelayNs,
+    {
+        self.send_command(commands::DISPOFF)?;
+        self.send_command(commands::SLPIN)?;
+        delay.delay_ms(120);
+        self.reset.power_off().map_err(DriverError::ResetError)?;
+        self.forget_panel_state();
+        Ok(())
+    }
+
+    /// Enters deep standby mode (DSTBON), the lowest power state of the panel.
+    ///
+    /// Deep standby discards all register state, so the panel does not
+    /// respond to commands until it is woken with
+    /// [`Self::wake_from_deep_standby`], which performs a full
+    /// re-initialization.
+    pub fn deep_standby(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command_with_data(commands::DSTBON, &[0x01])?;
+        self.panel_state = PanelState::RESET;
+        Ok(())
+    }
+
+    /// Wakes the panel from deep standby.
+    ///
+    /// Re-runs [`Self::hard_reset`] and [`Self::initialize_display`], since
+    /// deep standby loses the register state. Inversion, partial area,
+    /// color temperature, current limiting and frame rate are back at their
+    /// defaults afterwards, while the brightness is rewritten during
+    /// initialization; call [`Self::flush`] to restore the image.
+    pub fn wake_from_deep_standby<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>>
+    where
+        DELAY: DelayNs,
+    {
+        self.hard_reset()?;
+        self.initialize_display(delay)
+    }
+
+    /// Software reset (SWRESET), followed by the 120ms the controller needs before accepting commands.
+    ///
+    /// Resets the panel registers without toggling the reset line, which
+    /// helps when the reset pin is shared or not wired. The panel is left
+    /// asleep with the display off, so call [`Self::initialize_display`]
+    /// afterwards. The driver's framebuffer is not cleared; flush it to
+    /// restore the image.
+    pub fn soft_reset<DELAY>(
+        &mut self,
+        delay: &mut DELAY,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>>
+    where
+        DELAY: DelayNs,
+    {
+        self.send_command(commands::SWRESET)?;
+        delay.delay_ms(120);
+        self.forget_panel_state();
+        Ok(())
+    }
+
+    /// Returns the cached panel register state to its reset defaults.
+    fn forget_panel_state(&mut self) {
+        self.inverted = false;
+        self.all_pixels_forced = false;
+        self.partial_area = None;
+        self.partial_mode = false;
+        self.rad_acl = 0x00;
+        self.frame_rate_level = None;
+        self.pixel_format = PixelFormatOptions::default();
+        self.panel_state = PanelState::RESET;
+    }
+
     /// Turns the display panel off
     pub fn display_off(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
-        self.send_command(commands::DISPOFF)
+        self.send_command(commands::DISPOFF)?;
+        self.panel_state.on = false;
+        Ok(())
     }
 
     /// Turns the display panel on
     pub fn display_on(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
-        self.send_command(commands::DISPON)
+        self.send_command(commands::DISPON)?;
+        self.panel_state.on = true;
+        Ok(())
     }
 
-    /// Sets the active drawing window on the display RAM.
-    pub fn set_window(
-        &mut self,
-        x_start: u16,
-        y_start: u16,
-        x_end: u16,
-        y_end: u16,
-    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
-        let width = x_end.saturating_sub(x_start).saturating_add(1);
-        let height = y_end.saturating_sub(y_start).saturating_add(1);
+    /// Returns whether the display is on and awake, as tracked by the driver.
+    ///
+    /// Flushing is still allowed while the panel is off or asleep, e.g. to
+    /// load display RAM before [`Self::display_on`], but nothing shows until
+    /// it is awake and on. Commands sent through [`Self::write_command`] are
+    /// not tracked.
+    pub fn panel_state(&self) -> PanelState {
+        self.panel_state
+    }
 
-        if x_start % 2 != 0 || width % 2 != 0 {
-            return Err(DriverError::InvalidConfiguration(
-                "x_start and width must both be even",
-            ));
-        }
-        if y_start % 2 != 0 || height % 2 != 0 {
-            return Err(DriverError::InvalidConfiguration(
-                "y_start and height must both be even",
-            ));
+    /// Returns `true` if the display is on (DISPON).
+    pub fn is_display_on(&self) -> bool {
+        self.panel_state.on
+    }
+
+    /// Returns `true` if the panel is in sleep mode or deep standby.
+    pub fn is_sleeping(&self) -> bool {
+        self.panel_state.sleeping
+    }
+
+    /// Enables (INVON) or disables (INVOFF) display color inversion.
+    pub fn set_inversion(&mut self, on: bool) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if on {
+            self.send_command(commands::INVON)?;
+        } else {
+            self.send_command(commands::INVOFF)?;
+        }
+        self.inverted = on;
+        Ok(())
+    }
+
+    /// Returns `true` if display color inversion is enabled.
+    pub fn is_inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// Enters (IDMON) or leaves (IDMOFF) idle mode.
+    ///
+    /// Idle mode lowers power by reducing the panel to 8 colors (the MSB of
+    /// each channel), which suits mostly static screens such as a watch face.
+    /// The framebuffer and display RAM are unchanged; only how the panel
+    /// renders them differs, so full color returns as soon as idle mode is left.
+    pub fn set_idle_mode(&mut self, on: bool) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if on {
+            self.send_command(commands::IDMON)
+        } else {
+            self.send_command(commands::IDMOFF)
+        }
+    }
+
+    /// Turns every pixel on (ALLPON) or off (ALLPOFF) without touching display RAM.
+    ///
+    /// This is an instant full-screen white or black. The panel stays in this
+    /// state, ignoring display RAM and the framebuffer, until the next
+    /// [`Self::flush`], which returns it to normal display mode before sending
+    /// the frame.
+    pub fn clear_all_pixels(
+        &mut self,
+        on: bool,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if on {
+            self.send_command(commands::ALLPON)?;
+        } else {
+            self.send_command(commands::ALLPOFF)?;
+        }
+        self.all_pixels_forced = true;
+        Ok(())
+    }
+
+    /// Leaves ALLPON/ALLPOFF if it was entered through [`Self::clear_all_pixels`].
+    fn restore_normal_display(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if self.all_pixels_forced {
+            self.send_command(commands::NORON)?;
+            self.all_pixels_forced = false;
+            self.partial_mode = false;
+        }
+        Ok(())
+    }
+
+    /// Sets the partial display area (PTLAR) to panel rows `start_row..=end_row`.
+    ///
+    /// Once partial display mode is entered with [`Self::enable_partial_mode`],
+    /// [`Self::flush`] only transmits the active rows. Drawing outside the
+    /// area still updates the framebuffer, so the full image is intact once
+    /// the panel returns to normal mode, but those rows are not transmitted
+    /// while partial mode is on.
+    pub fn set_partial_area(
+        &mut self,
+        start_row: u16,
+        end_row: u16,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if start_row > end_row || end_row >= self.config.height {
+            return Err(DriverError::InvalidConfiguration(
+                "Partial area rows must satisfy start_row <= end_row < height",
+            ));
+        }
+        self.send_command_with_data(commands::PTLAR, &range_params(start_row, end_row))?;
+        self.partial_area = Some((start_row, end_row));
+        Ok(())
+    }
+
+    /// Forgets the area set by [`Self::set_partial_area`].
+    ///
+    /// The next [`Self::flush`] sends the full framebuffer again, even in
+    /// partial display mode. PTLAR itself is left unchanged.
+    pub fn clear_partial_area(&mut self) {
+        self.partial_area = None;
+    }
+
+    /// Enters partial display mode (PTLON).
+    ///
+    /// Only the rows set with [`Self::set_partial_area`] are lit; the rest of
+    /// the panel is driven dark, which saves power on AMOLED panels. Until
+    /// [`Self::enable_normal_mode`], [`Self::flush`] only transmits those rows.
+    pub fn enable_partial_mode(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command(commands::PTLON)?;
+        self.partial_mode = true;
+        Ok(())
+    }
+
+    /// Returns to normal display mode (NORON), lighting the whole panel again.
+    ///
+    /// This also leaves ALLPON/ALLPOFF set by [`Self::clear_all_pixels`], and
+    /// lifts the flush restriction of partial mode.
+    pub fn enable_normal_mode(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command(commands::NORON)?;
+        self.all_pixels_forced = false;
+        self.partial_mode = false;
+        Ok(())
+    }
+
+    /// Defines the vertical scrolling area (VSCRDEF).
+    ///
+    /// The panel is split into `top_fixed` rows that stay in place, a
+    /// `scroll_height` row scrolling region, and `bottom_fixed` rows that stay
+    /// in place. The three must add up to the display height.
+    pub fn set_scroll_area(
+        &mut self,
+        top_fixed: u16,
+        scroll_height: u16,
+        bottom_fixed: u16,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if top_fixed as u32 + scroll_height as u32 + bottom_fixed as u32
+            != self.config.height as u32
+        {
+            return Err(DriverError::InvalidConfiguration(
+                "Scroll areas must add up to the display height",
+            ));
+        }
+        let [top_high, top_low, scroll_high, scroll_low] = range_params(top_fixed, scroll_height);
+        let [bottom_high, bottom_low] = bottom_fixed.to_be_bytes();
+        self.send_command_with_data(
+            commands::VSCRDEF,
+            &[
+                top_high,
+                top_low,
+                scroll_high,
+                scroll_low,
+                bottom_high,
+                bottom_low,
+            ],
+        )
+    }
+
+    /// Sets the display RAM row shown at the top of the scrolling area (VSCRSADD).
+    ///
+    /// Updating this each frame scrolls the area set with
+    /// [`Self::set_scroll_area`] without rewriting display RAM.
+    pub fn set_scroll_start(
+        &mut self,
+        line: u16,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if line >= self.config.height {
+            return Err(DriverError::InvalidConfiguration(
+                "Scroll start line must be below the display height",
+            ));
+        }
+        self.send_command_with_data(
+            commands::VSCRSADD,
+            &[(line >> 8) as u8, (line & 0xFF) as u8],
+        )
+    }
+
+    /// Shifts the image in display RAM by `dx` columns and `dy` rows.
+    ///
+    /// A standard AMOLED burn-in mitigation: calling this periodically with a
+    /// small offset moves the whole picture without re-rendering. The
+    /// framebuffer is unchanged; only the window written on flush moves.
+    ///
+    /// The shifted frame must stay within the controller's
+    /// [`MAX_COLUMNS`] x [`MAX_ROWS`] RAM. In portrait the usable range is
+    /// `-col_offset..=MAX_COLUMNS - width - col_offset` horizontally and
+    /// `-row_offset..=MAX_ROWS - height - row_offset` vertically (swapped in
+    /// landscape). Larger shifts are clipped to that range, and odd shifts are
+    /// rounded towards zero to keep windows even-aligned. Returns the shift
+    /// actually applied. Pixels shifted past the visible area are not shown,
+    /// and rows/columns uncovered by the shift keep stale RAM content.
+    pub fn set_pixel_shift(&mut self, dx: i8, dy: i8) -> (i16, i16) {
+        let (width, height) = self.dimensions();
+        let (offset_x, offset_y) = self.ram_offset();
+        let (ram_columns, ram_rows) = if self.orientation.is_landscape() {
+            (MAX_ROWS, MAX_COLUMNS)
+        } else {
+            (MAX_COLUMNS, MAX_ROWS)
+        };
+        let clip = |shift: i8, offset: u16, ram: u16, size: u16| {
+            let min = -(offset as i16);
+            let max = (ram as i16 - size as i16 - offset as i16).max(min);
+            let shift = (shift as i16).clamp(min, max);
+            shift - shift % 2
+        };
+        self.pixel_shift = (
+            clip(dx, offset_x, ram_columns, width),
+            clip(dy, offset_y, ram_rows, height),
+        );
+        self.pixel_shift
+    }
+
+    /// Moves the image to the next position of a small square orbit and flushes it.
+    ///
+    /// Opt-in burn-in mitigation for always-on screens, meant to be called
+    /// from a timer. Each call steps the pixel shift through `(0, 0)`,
+    /// `(2, 0)`, `(2, 2)` and `(0, 2)`, then sends the whole framebuffer at
+    /// the new position. Calling it every one to five minutes keeps static
+    /// content such as a clock face from wearing the same pixels. Returns the
+    /// shift applied, which may be clipped as in [`Self::set_pixel_shift`].
+    pub fn burn_in_shift(&mut self) -> Result<(i16, i16), DriverError<IFACE::Error, RST::Error>> {
+        const ORBIT: [(i8, i8); 4] = [(0, 0), (2, 0), (2, 2), (0, 2)];
+        self.burn_in_step = (self.burn_in_step + 1) % ORBIT.len() as u8;
+        let (dx, dy) = ORBIT[self.burn_in_step as usize];
+        let shift = self.set_pixel_shift(dx, dy);
+        self.flush()?;
+        Ok(shift)
+    }
+
+    /// Briefly shows the inverse image, then restores the previous inversion state.
+    ///
+    /// Opt-in burn-in mitigation built on INVON/INVOFF: showing the inverse
+    /// of a static image for a moment evens out pixel wear. A duration of
+    /// 200 to 500ms once every 10 to 30 minutes is a reasonable starting
+    /// point; longer or more frequent inversions are noticeable to the user.
+    /// The previous state is restored even if switching inversion on fails.
+    pub fn anti_burnin_invert<DELAY>(
+        &mut self,
+        duration_ms: u32,
+        delay: &mut DELAY,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>>
+    where
+        DELAY: DelayNs,
+    {
+        let inverted = self.inverted;
+        let result = self.set_inversion(!inverted);
+        if result.is_ok() {
+            delay.delay_ms(duration_ms);
+        }
+        let restored = self.set_inversion(inverted);
+        result.and(restored)
+    }
+
+    /// Returns the RAM offset of the visible area along the logical x and y axes.
+    fn ram_offset(&self) -> (u16, u16) {
+        self.config.ram_offset(self.orientation)
+    }
+
+    /// Sets the active drawing window on the display RAM.
+    ///
+    /// Coordinates are logical and zero-based; the configured column/row
+    /// offset and any pixel shift are added before CASET/RASET are written,
+    /// and the result must stay within the controller RAM.
+    pub fn set_window(
+        &mut self,
+        x_start: u16,
+        y_start: u16,
+        x_end: u16,
+        y_end: u16,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let (offset_x, offset_y) = self.ram_offset();
+        let (shift_x, shift_y) = self.pixel_shift;
+        let delta = (
+            (offset_x as i16 + shift_x) as u16,
+            (offset_y as i16 + shift_y) as u16,
+        );
+        let (caset, raset) = window_params(
+            (x_start, y_start, x_end, y_end),
+            self.dimensions(),
+            delta,
+            self.orientation.is_landscape(),
+        )
+        .map_err(DriverError::InvalidConfiguration)?;
+
+        #[cfg(any(test, feature = "test-internals"))]
+        {
+            self.window = Some((x_start, y_start, x_end, y_end));
+        }
+
+        self.send_command_with_data(commands::CASET, &caset)?;
+        self.send_command_with_data(commands::RASET, &raset)
+    }
+
+    /// Sets the Memory Data Access Control (MADCTR) register.
+    pub fn set_madctr(&mut self, value: u8) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command_with_data(commands::MADCTR, &[value])?;
+        self.madctr = value;
+        Ok(())
+    }
+
+    /// Returns the last value written to MADCTR.
+    pub fn madctr(&self) -> u8 {
+        self.madctr
+    }
+
+    /// Sets the display orientation.
+    ///
+    /// Landscape orientations swap the logical width and height reported to
+    /// `embedded-graphics`. The framebuffer is laid out in logical
+    /// coordinates, so its contents should be redrawn after changing
+    /// orientation. Any pixel shift and interrupted [`Self::flush_resumable`]
+    /// are reset.
+    pub fn set_orientation(
+        &mut self,
+        orientation: Orientation,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.set_madctr(orientation.madctr() | self.pixel_format.madctr_bits())?;
+        self.orientation = orientation;
+        self.pixel_shift = (0, 0);
+        self.resume_row = None;
+        Ok(())
+    }
+
+    /// Returns the current display orientation.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Returns the MADCTR value for the current orientation and pixel format options.
+    fn orientation_madctr(&self) -> u8 {
+        self.orientation.madctr() | self.pixel_format.madctr_bits()
+    }
+
+    /// Sets the interface color order.
+    ///
+    /// The color order is applied through the MADCTR RGB bit, keeping the
+    /// current orientation, and survives later orientation changes.
+    ///
+    /// RGB565 byte order is set separately, with
+    /// [`Self::set_rgb565_endianness`]: the panel's COLOPT endianness bit is
+    /// not publicly documented, so the driver changes the byte order it
+    /// stores in the framebuffer instead of reconfiguring the panel. A panel
+    /// that comes up in BGR needs only this call; [`Self::set_bgr`] is for
+    /// swapping channels in software when the MADCTR bit is not an option.
+    pub fn set_pixel_format_option(
+        &mut self,
+        opts: PixelFormatOptions,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.set_madctr((self.madctr & !madctr::RGB) | opts.madctr_bits())?;
+        self.pixel_format = opts;
+        Ok(())
+    }
+
+    /// Returns the last options written with [`Self::set_pixel_format_option`].
+    pub fn pixel_format_option(&self) -> PixelFormatOptions {
+        self.pixel_format
+    }
+
+    /// Swaps the red and blue channels when drawing into the framebuffer.
+    ///
+    /// This only changes how `draw_iter`, `fill_contiguous`, `fill_solid`,
+    /// [`Self::load_and_convert`] and [`Self::render_line`] lay out colors in
+    /// the framebuffer; no command is sent and the MADCTR
+    /// BGR bit is left as is. Pixels already drawn are not converted. Gray8
+    /// is unaffected.
+    pub fn set_bgr(&mut self, bgr: bool) {
+        self.bgr = bgr;
+    }
+
+    /// Returns `true` if drawing swaps the red and blue channels.
+    pub fn bgr(&self) -> bool {
+        self.bgr
+    }
+
+    /// Sets the byte order in which drawing stores RGB565 pixels in the framebuffer.
+    ///
+    /// Defaults to [`Endianness::Big`], the order the panel expects unless
+    /// configured otherwise. Use [`Endianness::Little`] for setups that
+    /// expect the low byte first on the wire. Other color modes are
+    /// unaffected, and pixels already drawn are not converted.
+    pub fn set_rgb565_endianness(&mut self, endianness: Endianness) {
+        self.rgb565_endianness = endianness;
+    }
+
+    /// Returns the byte order used for RGB565 pixels in the framebuffer.
+    pub fn rgb565_endianness(&self) -> Endianness {
+        self.rgb565_endianness
+    }
+
+    /// Returns the encoder drawing uses for the current color mode and layout options.
+    pub(crate) fn pixel_encoder(&self) -> PixelEncoder {
+        PixelEncoder::new(self.color_mode, self.bgr, self.rgb565_endianness)
+    }
+
+    /// Returns the logical `(width, height)` for the current orientation.
+    ///
+    /// This is also the layout of the framebuffer.
+    pub(crate) fn dimensions(&self) -> (u16, u16) {
+        self.config.logical_size(self.orientation)
+    }
+
+    /// Sets the rotation applied in software to drawing coordinates.
+    ///
+    /// The framebuffer keeps its layout and MADCTR is not written, so no
+    /// extra memory is needed. `size()` reports the rotated dimensions, while
+    /// the framebuffer accessors, dirty tracking and flush methods keep using
+    /// framebuffer coordinates. The whole framebuffer is marked dirty; its
+    /// contents should be redrawn, and an interrupted
+    /// [`Self::flush_resumable`] starts over.
+    pub fn set_software_rotation(&mut self, rotation: SoftwareRotation) {
+        self.software_rotation = rotation;
+        self.resume_row = None;
+        let (width, height) = self.dimensions();
+        self.mark_dirty(0, 0, width - 1, height - 1);
+    }
+
+    /// Returns the rotation applied in software to drawing coordinates.
+    pub fn software_rotation(&self) -> SoftwareRotation {
+        self.software_rotation
+    }
+
+    /// Returns the `(width, height)` seen by `embedded-graphics`, including software rotation.
+    pub(crate) fn draw_dimensions(&self) -> (u16, u16) {
+        let (width, height) = self.dimensions();
+        if self.software_rotation.swaps_axes() {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    /// Maps an in-bounds drawing coordinate to its framebuffer coordinate.
+    pub(crate) fn to_framebuffer(&self, x: u16, y: u16) -> (u16, u16) {
+        let (width, height) = self.dimensions();
+        match self.software_rotation {
+            SoftwareRotation::None => (x, y),
+            SoftwareRotation::Rotate90 => (width - 1 - y, x),
+            SoftwareRotation::Rotate180 => (width - 1 - x, height - 1 - y),
+            SoftwareRotation::Rotate270 => (y, height - 1 - x),
+        }
+    }
+
+    /// Temporarily sets MADCTR to `value` while running `f`, then restores the previous value.
+    ///
+    /// The previous value is restored even if `f` fails; the error from `f`
+    /// takes precedence over one from the restore.
+    pub fn with_madctr(
+        &mut self,
+        value: u8,
+        f: impl FnOnce(&mut Self) -> Result<(), DriverError<IFACE::Error, RST::Error>>,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let previous = self.madctr;
+        self.set_madctr(value)?;
+        let result = f(self);
+        let restored = self.set_madctr(previous);
+        result.and(restored)
+    }
+
+    /// Sets the display brightness (0x00 - 0xFF for RM690B0).
+    pub fn set_brightness(
+        &mut self,
+        value: u8,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command_with_data(commands::WRDISBV, &[value])?;
+        self.brightness = value;
+        Ok(())
+    }
+
+    /// Writes the display control bits (WRCTRLD).
+    ///
+    /// [`Self::initialize_display`] writes [`DisplayControl::default`],
+    /// enabling brightness control so [`Self::set_brightness`] takes effect
+    /// on panels that gate it.
+    pub fn set_display_control(
+        &mut self,
+        ctrl: DisplayControl,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command_with_data(commands::WRCTRLD, &[ctrl.bits()])
+    }
+
+    /// Returns the last brightness written with WRDISBV.
+    ///
+    /// This is the driver's cached value; use `read_brightness` to query
+    /// the panel on interfaces that can read.
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Sets the panel refresh rate (FR_LEVEL).
+    ///
+    /// 30 and 60 Hz work in every color mode; 90 Hz only with RGB565 and
+    /// Gray8. Unsupported combinations return
+    /// [`DriverError::InvalidConfiguration`] without writing the register.
+    pub fn set_frame_rate(
+        &mut self,
+        rate: FrameRate,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if !rate.supports(self.color_mode) {
+            return Err(DriverError::InvalidConfiguration(
+                "Frame rate is not supported in this color mode",
+            ));
+        }
+        self.set_frame_rate_level(rate.fr_level())
+    }
+
+    /// Returns the refresh rate last set, [`FrameRate::Hz60`] at the power-on
+    /// default or after a raw level that is not one of the [`FrameRate`] rates.
+    pub fn frame_rate(&self) -> FrameRate {
+        self.frame_rate_level
+            .and_then(FrameRate::from_fr_level)
+            .unwrap_or(FrameRate::Hz60)
+    }
+
+    /// Writes the frame rate level register (FR_LEVEL) and caches its value.
+    ///
+    /// Prefer [`Self::set_frame_rate`]; this writes `level` unchecked, for
+    /// levels outside the [`FrameRate`] rates.
+    pub fn set_frame_rate_level(
+        &mut self,
+        level: u8,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command_with_data(commands::FR_LEVEL, &[level])?;
+        self.frame_rate_level = Some(level);
+        Ok(())
+    }
+
+    /// Returns the level last written with [`Self::set_frame_rate_level`], or
+    /// `None` if the panel is still at its power-on default.
+    pub fn frame_rate_level(&self) -> Option<u8> {
+        self.frame_rate_level
+    }
+
+    /// Selects a color temperature preset (COLORTEMP).
+    ///
+    /// COLORTEMP and WRRADACL are the same register (0x55). Only the
+    /// [`ColorTemp::MASK`] bits of the cached value are replaced, so the
+    /// level set with [`Self::set_acl`] is preserved.
+    pub fn set_color_temperature(
+        &mut self,
+        temp: ColorTemp,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.set_rad_acl((self.rad_acl & !ColorTemp::MASK) | temp.bits())
+    }
+
+    /// Sets the automatic current limiting level (WRRADACL).
+    ///
+    /// Shares register 0x55 with COLORTEMP; only the [`AclLevel::MASK`] bits
+    /// are replaced, so the preset set with [`Self::set_color_temperature`]
+    /// is preserved.
+    pub fn set_acl(
+        &mut self,
+        level: AclLevel,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.set_rad_acl((self.rad_acl & !AclLevel::MASK) | level.bits())
+    }
+
+    /// Writes register 0x55, shared by COLORTEMP and WRRADACL, and caches its value.
+    ///
+    /// Every write sets both the color temperature and the current limiting
+    /// level; [`Self::set_color_temperature`] and [`Self::set_acl`] update
+    /// one of them and keep the other.
+    pub fn set_rad_acl(&mut self, value: u8) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command_with_data(commands::WRRADACL, &[value])?;
+        self.rad_acl = value;
+        Ok(())
+    }
+
+    /// Returns the value last written to register 0x55.
+    pub fn rad_acl(&self) -> u8 {
+        self.rad_acl
+    }
+
+    /// Uploads a gamma curve.
+    ///
+    /// Switches to the curve's command page, writes the positive then the
+    /// negative coefficients, and switches back to the user command page,
+    /// even if a write fails. A curve whose coefficients would run past
+    /// register 0xFF is rejected before anything is sent.
+    pub fn set_gamma(
+        &mut self,
+        curve: &GammaCurve,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let last = GAMMA_POINTS as u8 - 1;
+        if curve.positive_register.checked_add(last).is_none()
+            || curve.negative_register.checked_add(last).is_none()
+        {
+            return Err(DriverError::InvalidConfiguration(
+                "Gamma registers must not run past 0xFF",
+            ));
+        }
+
+        let registers = |first: u8| (0..=last).map(move |i| first + i);
+        self.send_command_with_data(commands::CMD_PAGE, &[curve.page])?;
+        let result = registers(curve.positive_register)
+            .zip(curve.positive)
+            .chain(registers(curve.negative_register).zip(curve.negative))
+            .try_for_each(|(register, value)| self.send_command_with_data(register, &[value]));
+        let restored = self.send_command_with_data(commands::CMD_PAGE, &[0x00]);
+        result.and(restored)
+    }
+
+    /// Writes the high brightness mode register (HBM_MODE).
+    ///
+    /// `mode` is sent as is: the values that enable and disable HBM are not
+    /// part of the public RM690B0 command description, so take them from the
+    /// module vendor's datasheet. While HBM is enabled, the level set with
+    /// [`Self::set_hbm_brightness`] replaces the normal WRDISBV brightness.
+    /// The panel may limit HBM output to protect itself from overheating, so
+    /// it is meant for short periods of direct sunlight rather than continuous use.
+    pub fn set_hbm_mode(&mut self, mode: u8) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command_with_data(commands::HBM_MODE, &[mode])
+    }
+
+    /// Sets the brightness used while high brightness mode is enabled (WRHBM).
+    pub fn set_hbm_brightness(
+        &mut self,
+        level: u8,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command_with_data(commands::WRHBM, &[level])
+    }
+
+    /// Ramps the brightness from its current value to `target` over `duration_ms`.
+    ///
+    /// Writes WRDISBV once per step, with one step every 4ms (and never more
+    /// steps than brightness levels to cross). The last write is always
+    /// `target`.
+    pub fn fade_brightness<DELAY>(
+        &mut self,
+        target: u8,
+        duration_ms: u32,
+        delay: &mut DELAY,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>>
+    where
+        DELAY: DelayNs,
+    {
+        const STEP_MS: u32 = 4;
+
+        let start = self.brightness as i32;
+        let distance = target as i32 - start;
+        let steps = (duration_ms / STEP_MS).clamp(1, distance.unsigned_abs().max(1));
+        let step_delay_us = duration_ms.saturating_mul(1000) / steps;
+
+        for step in 1..=steps {
+            let level = start + distance * step as i32 / steps as i32;
+            self.set_brightness(level as u8)?;
+            if step < steps {
+                delay.delay_us(step_delay_us);
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the contents of the framebuffer to the display RAM.
+    ///
+    /// A full frame from a static framebuffer goes through
+    /// [`ControllerInterface::send_frame_static`], letting DMA-capable
+    /// interfaces send it without a copy.
+    ///
+    /// In partial display mode ([`Self::enable_partial_mode`]), only the
+    /// panel rows set with [`Self::set_partial_area`] are transmitted,
+    /// widened to even boundaries. In landscape orientations those panel rows
+    /// are framebuffer columns, which are sent as a column band.
+    pub fn flush(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.restore_normal_display()?;
+        let (width, height) = self.dimensions();
+        if let Some((x_start, y_start, x_end, y_end)) = self.partial_mode_bounds() {
+            if self.orientation.is_landscape() {
+                return self.flush_bounds(x_start, y_start, x_end, y_end);
+            }
+            let stride = width as usize * self.color_mode.bytes_per_pixel();
+            self.set_window(x_start, y_start, x_end, y_end)?;
+            self.interface
+                .send_pixels(
+                    &self.framebuffer[y_start as usize * stride..(y_end as usize + 1) * stride],
+                )
+                .map_err(DriverError::InterfaceError)?;
+            return Ok(());
+        }
+
+        // Set window to full display
+        self.set_window(0, 0, width - 1, height - 1)?;
+        let result = match &mut self.framebuffer {
+            Framebuffer::Static(arr) => {
+                let (frame, result) = self.interface.send_frame_static(core::mem::take(arr));
+                *arr = frame;
+                result
+            }
+            #[cfg(feature = "alloc")]
+            Framebuffer::Heap(boxed) => self.interface.send_pixels(boxed),
+        };
+        result.map_err(DriverError::InterfaceError)
+    }
+
+    /// Returns the inclusive framebuffer bounds `flush` is limited to in partial
+    /// display mode, or `None` when the whole frame is sent.
+    ///
+    /// Panel rows are mapped to logical coordinates following the rotation
+    /// documented on [`Orientation`], and widened to even boundaries.
+    fn partial_mode_bounds(&self) -> Option<(u16, u16, u16, u16)> {
+        let rows = self.partial_area.filter(|_| self.partial_mode)?;
+        Some(partial_bounds(rows, self.orientation, self.dimensions()))
+    }
+
+    /// Fills the framebuffer with a test pattern and flushes it.
+    ///
+    /// A sanity check for board bring-up that doesn't need any drawing code:
+    /// color bars show swapped channels and stride errors at a glance. The
+    /// pattern is laid out in framebuffer coordinates, ignoring software
+    /// rotation, and overwrites the framebuffer contents.
+    pub fn draw_test_pattern(
+        &mut self,
+        pattern: TestPattern,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let (width, height) = self.dimensions();
+        let bytes_per_pixel = self.color_mode.bytes_per_pixel();
+        let encoder = self.pixel_encoder();
+        let stride = self.stride();
+        for (y, row) in self.framebuffer.chunks_exact_mut(stride).enumerate() {
+            for (x, pixel) in row.chunks_exact_mut(bytes_per_pixel).enumerate() {
+                let color = pattern.color_at(x as u16, y as u16, width, height);
+                encoder.encode(color, pixel);
+            }
+        }
+        self.mark_dirty(0, 0, width - 1, height - 1);
+        self.flush()
+    }
+
+    /// Calls [`Self::flush`], retrying up to `retries` more times if it fails.
+    ///
+    /// Meant for transient bus errors, e.g. from EMI; `flush` re-sends the
+    /// whole window, so retrying is safe. The delay before retry `n` (from 1)
+    /// is `n * backoff_ms`. Returns the last error if every attempt fails.
+    pub fn flush_with_retries<DELAY>(
+        &mut self,
+        retries: u8,
+        delay: &mut DELAY,
+        backoff_ms: u32,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>>
+    where
+        DELAY: DelayNs,
+    {
+        let mut result = self.flush();
+        for attempt in 1..=retries as u32 {
+            if result.is_ok() {
+                break;
+            }
+            delay.delay_ms(backoff_ms.saturating_mul(attempt));
+            result = self.flush();
+        }
+        result
+    }
+
+    /// Enables the tearing effect output (TEON) in the given mode.
+    ///
+    /// The mode is kept and applied again by [`Self::initialize_display`].
+    pub fn enable_tearing_effect(
+        &mut self,
+        mode: TearMode,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command_with_data(commands::TEON, &[mode.teon()])?;
+        self.tear_mode = Some(mode);
+        Ok(())
+    }
+
+    /// Disables the tearing effect output (TEOFF), for boards that don't wire the TE pin.
+    ///
+    /// The output stays disabled across [`Self::initialize_display`].
+    pub fn disable_tearing_effect(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.send_command(commands::TEOFF)?;
+        self.tear_mode = None;
+        Ok(())
+    }
+
+    /// Returns the tearing effect mode, or `None` if the output is disabled.
+    pub fn tear_mode(&self) -> Option<TearMode> {
+        self.tear_mode
+    }
+
+    /// Sets the scanline at which the TE output pulses (STESL).
+    ///
+    /// `line` is a panel row and must be below the display height.
+    pub fn set_tear_scanline(
+        &mut self,
+        line: u16,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if line >= self.config.height {
+            return Err(DriverError::InvalidConfiguration(
+                "Tear scanline must be below the display height",
+            ));
+        }
+        // A single row, high byte first like the rows of `range_params`.
+        self.send_command_with_data(commands::STESL, &line.to_be_bytes())
+    }
+
+    /// Waits for the next TE pulse, then writes the framebuffer like [`Self::flush`].
+    ///
+    /// Starting the transfer at the beginning of vertical blanking keeps the
+    /// write ahead of the panel scan, avoiding visible tearing. The TE output
+    /// must be enabled, which [`Self::initialize_display`] does unless the
+    /// builder disabled it.
+    pub fn flush_synced(
+        &mut self,
+        te: &mut impl TearingEffectPin,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        te.wait_for_vsync();
+        self.flush()
+    }
+
+    /// Writes an RGB888 framebuffer to the panel in a different wire format.
+    ///
+    /// Keeps drawing at full RGB888 precision while sending fewer bytes over
+    /// the bus; RGB565 cuts the transfer by a third. Pixels are converted one
+    /// row at a time into a stack buffer and sent row by row, honouring the
+    /// BGR and RGB565 byte order settings. In partial display mode only the
+    /// rows [`Self::flush`] would send are converted. The panel's COLMOD must
+    /// already match `wire`; this does not change it.
+    pub fn flush_converting(
+        &mut self,
+        wire: ColorMode,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if self.color_mode != ColorMode::Rgb888 {
+            return Err(DriverError::InvalidConfiguration(
+                "flush_converting requires an RGB888 framebuffer",
+            ));
+        }
+        if wire == ColorMode::Rgb888 {
+            return self.flush();
+        }
+
+        self.restore_normal_display()?;
+        let (width, height) = self.dimensions();
+        let (x_start, y_start, x_end, y_end) =
+            self.partial_mode_bounds()
+                .unwrap_or((0, 0, width - 1, height - 1));
+        self.set_window(x_start, y_start, x_end, y_end)?;
+
+        let source = self.pixel_encoder();
+        let target = PixelEncoder::new(wire, self.bgr, self.rgb565_endianness);
+        let stride = self.stride();
+        let columns = (x_end - x_start + 1) as usize;
+        let mut row = [0u8; ROW_BUFFER_LEN];
+        for y in y_start..=y_end {
+            let offset = y as usize * stride + x_start as usize * 3;
+            let src = &self.framebuffer[offset..offset + columns * 3];
+            let out = &mut row[..columns * wire.bytes_per_pixel()];
+            for (src, dst) in src
+                .chunks_exact(3)
+                .zip(out.chunks_exact_mut(wire.bytes_per_pixel()))
+            {
+                target.encode(source.decode(src), dst);
+            }
+            let result = if y == y_start {
+                self.interface.send_pixels_start(out)
+            } else {
+                self.interface.send_pixels_continue(out)
+            };
+            result.map_err(DriverError::InterfaceError)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the framebuffer in bands of `rows_per_chunk` rows, resuming after a failed transfer.
+    ///
+    /// If a band fails, the rows before it are known to have reached the
+    /// panel; the error is returned and the next call continues from the
+    /// failed band instead of resending the whole frame. The row to resume
+    /// from is available through [`Self::resume_row`]. `rows_per_chunk` must
+    /// be even and non-zero.
+    pub fn flush_resumable(
+        &mut self,
+        rows_per_chunk: u16,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if rows_per_chunk == 0 || rows_per_chunk % 2 != 0 {
+            return Err(DriverError::InvalidConfiguration(
+                "rows_per_chunk must be even and non-zero",
+            ));
+        }
+        self.restore_normal_display()?;
+        let (width, height) = self.dimensions();
+        let start = self
+            .resume_row
+            .take()
+            .filter(|&row| row < height)
+            .unwrap_or(0);
+        if let Err(e) = self.set_window(0, start, width - 1, height - 1) {
+            self.resume_row = Some(start);
+            return Err(e);
+        }
+
+        let stride = self.stride();
+        let mut row = start;
+        while row < height {
+            let end = row.saturating_add(rows_per_chunk).min(height);
+            let band = &self.framebuffer[row as usize * stride..end as usize * stride];
+            let result = if row == start {
+                self.interface.send_pixels_start(band)
+            } else {
+                self.interface.send_pixels_continue(band)
+            };
+            if let Err(e) = result {
+                self.resume_row = Some(row);
+                return Err(DriverError::InterfaceError(e));
+            }
+            row = end;
         }
-        if x_end < x_start
-            || y_end < y_start
-            || x_end >= self.config.width
-            || y_end >= self.config.height
-        {
-            return Err(DriverError::InvalidConfiguration(
-                "Invalid window dimensions",
-            ));
+        Ok(())
+    }
+
+    /// Returns the row an interrupted [`Self::flush_resumable`] will resume from, if any.
+    pub fn resume_row(&self) -> Option<u16> {
+        self.resume_row
+    }
+
+    /// Discards the progress of an interrupted [`Self::flush_resumable`], so the next call sends the whole frame.
+    pub fn cancel_resume(&mut self) {
+        self.resume_row = None;
+    }
+
+    /// Extends the dirty region to include the given inclusive bounds.
+    pub(crate) fn mark_dirty(&mut self, x_start: u16, y_start: u16, x_end: u16, y_end: u16) {
+        self.extend_dirty_bounds(x_start, y_start, x_end, y_end);
+        for y in y_start..=y_end {
+            self.mark_row_dirty(y);
         }
+    }
 
-        // CASET
-        self.send_command_with_data(
-            commands::CASET,
-            &[
-                (x_start >> 8) as u8,
-                (x_start & 0xFF) as u8,
-                (x_end >> 8) as u8,
-                (x_end & 0xFF) as u8,
-            ],
-        )?;
+    /// Extends the dirty bounding box without touching the row bitmap.
+    pub(crate) fn extend_dirty_bounds(
+        &mut self,
+        x_start: u16,
+        y_start: u16,
+        x_end: u16,
+        y_end: u16,
+    ) {
+        self.dirty = Some(match self.dirty {
+            Some((x0, y0, x1, y1)) => (
+                x0.min(x_start),
+                y0.min(y_start),
+                x1.max(x_end),
+                y1.max(y_end),
+            ),
+            None => (x_start, y_start, x_end, y_end),
+        });
+    }
 
-        // RASET
-        self.send_command_with_data(
-            commands::RASET,
-            &[
-                (y_start >> 8) as u8,
-                (y_start & 0xFF) as u8,
-                (y_end >> 8) as u8,
-                (y_end & 0xFF) as u8,
-            ],
-        )?;
+    /// Sets the dirty bit of a single row.
+    pub(crate) fn mark_row_dirty(&mut self, y: u16) {
+        if let Some(word) = self.dirty_rows.get_mut(y as usize / 32) {
+            *word |= 1 << (y % 32);
+        }
+    }
+
+    /// Clears the dirty bit of a single row.
+    fn clear_row_dirty(&mut self, y: u16) {
+        if let Some(word) = self.dirty_rows.get_mut(y as usize / 32) {
+            *word &= !(1 << (y % 32));
+        }
+    }
+
+    /// Returns `true` if the row was drawn since the last dirty flush.
+    fn is_row_dirty(&self, y: u16) -> bool {
+        self.dirty_rows
+            .get(y as usize / 32)
+            .is_some_and(|word| word & (1 << (y % 32)) != 0)
+    }
+
+    /// Marks the whole display as dirty, e.g. after writing the framebuffer directly.
+    pub fn mark_all_dirty(&mut self) {
+        let (width, height) = self.dimensions();
+        self.mark_dirty(0, 0, width - 1, height - 1);
+    }
+
+    /// Forgets all tracked changes without flushing them.
+    pub fn clear_dirty(&mut self) {
+        self.dirty = None;
+        self.dirty_rows.fill(0);
+    }
+
+    /// Returns the bounding box of everything drawn since the last dirty flush.
+    ///
+    /// Lets the application pick its own flush policy, e.g. skipping a frame
+    /// or choosing between [`Self::flush`] and [`Self::partial_flush_area`];
+    /// call [`Self::clear_dirty`] once the region has been handled.
+    pub fn dirty_region(&self) -> Option<Rectangle> {
+        self.dirty.map(|(x_start, y_start, x_end, y_end)| {
+            Rectangle::with_corners(
+                Point::new(x_start as i32, y_start as i32),
+                Point::new(x_end as i32, y_end as i32),
+            )
+        })
+    }
+
+    /// Returns the number of rows drawn since the last dirty flush.
+    pub fn dirty_row_count(&self) -> u32 {
+        self.dirty_rows.iter().map(|word| word.count_ones()).sum()
+    }
+
+    /// Flushes only the region drawn since the last dirty flush, then resets it.
+    ///
+    /// The region is widened to even boundaries to satisfy the window
+    /// alignment rules. Does nothing if nothing was drawn.
+    pub fn flush_dirty(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let Some((x_start, y_start, x_end, y_end)) = self.dirty_flush_bounds() else {
+            return Ok(());
+        };
+        self.flush_bounds(x_start, y_start, x_end, y_end)?;
+        self.clear_dirty();
         Ok(())
     }
 
-    /// Sets the Memory Data Access Control (MADCTR) register.
-    pub fn set_madctr(&mut self, value: u8) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
-        self.send_command_with_data(commands::MADCTR, &[value])
+    /// Returns the dirty region widened to even boundaries, as inclusive `(x_start, y_start, x_end, y_end)`.
+    fn dirty_flush_bounds(&self) -> Option<(u16, u16, u16, u16)> {
+        self.dirty
+            .map(|bounds| widen_to_even(bounds, self.dimensions()))
     }
 
-    /// Sets the display brightness (0x00 - 0xFF for RM690B0).
-    pub fn set_brightness(
+    /// Runs `draw` on a clean dirty state, then flushes what it drew.
+    ///
+    /// Bundles a render loop iteration in the right order: the dirty state
+    /// is cleared, `draw` runs, and the dirty region is sent as with
+    /// [`Self::flush_dirty`]. Nothing is sent if `draw` drew nothing.
+    pub fn present(
         &mut self,
-        value: u8,
+        draw: impl FnOnce(&mut Self),
+    ) -> Result<FrameStats, DriverError<IFACE::Error, RST::Error>> {
+        self.present_with(draw, || {})
+    }
+
+    /// Like [`Self::present`], but waits for the next TE pulse before flushing.
+    ///
+    /// The wait is skipped when the tearing effect output is disabled, as
+    /// no pulse would arrive.
+    pub fn present_synced(
+        &mut self,
+        te: &mut impl TearingEffectPin,
+        draw: impl FnOnce(&mut Self),
+    ) -> Result<FrameStats, DriverError<IFACE::Error, RST::Error>> {
+        let synced = self.tear_mode.is_some();
+        self.present_with(draw, || {
+            if synced {
+                te.wait_for_vsync();
+            }
+        })
+    }
+
+    /// Shared implementation of [`Self::present`] and [`Self::present_synced`].
+    fn present_with(
+        &mut self,
+        draw: impl FnOnce(&mut Self),
+        wait: impl FnOnce(),
+    ) -> Result<FrameStats, DriverError<IFACE::Error, RST::Error>> {
+        self.clear_dirty();
+        draw(self);
+        let dirty_area = self.dirty_region();
+        let Some((x_start, y_start, x_end, y_end)) = self.dirty_flush_bounds() else {
+            return Ok(FrameStats {
+                bytes_sent: 0,
+                dirty_area,
+            });
+        };
+        wait();
+        self.flush_bounds(x_start, y_start, x_end, y_end)?;
+        self.clear_dirty();
+        let pixels = (x_end - x_start + 1) as usize * (y_end - y_start + 1) as usize;
+        Ok(FrameStats {
+            bytes_sent: pixels * self.color_mode.bytes_per_pixel(),
+            dirty_area,
+        })
+    }
+
+    /// Flushes the rows drawn since the last dirty flush, then resets the dirty state.
+    ///
+    /// Contiguous dirty rows are coalesced into full-width bands, widened to
+    /// even rows, with one windowed transfer per band. Compared to
+    /// [`Self::flush_dirty`], updates that are far apart vertically do not
+    /// pull in the rows between them.
+    pub fn flush_dirty_rows(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let (width, height) = self.dimensions();
+        let mut pending: Option<(u16, u16)> = None;
+        let mut y = 0;
+        while y < height {
+            if !self.is_row_dirty(y) {
+                y += 1;
+                continue;
+            }
+            let start = y & !1;
+            let mut end = y;
+            while end + 1 < height && self.is_row_dirty(end + 1) {
+                end += 1;
+            }
+            let end = (end | 1).min(height - 1);
+            pending = match pending {
+                Some((band_start, band_end)) if start <= band_end + 1 => Some((band_start, end)),
+                Some((band_start, band_end)) => {
+                    self.flush_bounds(0, band_start, width - 1, band_end)?;
+                    Some((start, end))
+                }
+                None => Some((start, end)),
+            };
+            y = end + 1;
+        }
+        if let Some((band_start, band_end)) = pending {
+            self.flush_bounds(0, band_start, width - 1, band_end)?;
+        }
+        self.clear_dirty();
+        Ok(())
+    }
+
+    /// Enables double buffering with a caller-provided static back buffer.
+    ///
+    /// The buffer must be the same length as the primary framebuffer,
+    /// doubling the framebuffer memory in use.
+    pub fn enable_double_buffering_static(
+        &mut self,
+        buffer: &'static mut [u8],
     ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
-        self.send_command_with_data(commands::WRDISBV, &[value])
+        if buffer.len() != self.framebuffer.len() {
+            return Err(DriverError::InvalidConfiguration(
+                "Back buffer size does not match framebuffer size",
+            ));
+        }
+        self.back_buffer = Some(Framebuffer::Static(buffer));
+        Ok(())
     }
 
-    /// Writes the contents of the framebuffer to the display RAM.
-    pub fn flush(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
-        // Set window to full display
-        self.set_window(0, 0, self.config.width - 1, self.config.height - 1)?;
+    /// Enables double buffering with a heap-allocated back buffer.
+    ///
+    /// Allocates a second framebuffer of the same size as the primary one,
+    /// doubling the framebuffer memory in use.
+    #[cfg(feature = "alloc")]
+    pub fn enable_double_buffering_heap(&mut self) {
+        let buffer = alloc::vec![0u8; self.framebuffer.len()].into_boxed_slice();
+        self.back_buffer = Some(Framebuffer::Heap(buffer));
+    }
+
+    /// Swaps the framebuffers and starts sending the completed frame.
+    ///
+    /// The frame drawn so far becomes the back buffer and is handed to
+    /// [`ControllerInterface::send_pixels_deferred`]; drawing continues into
+    /// the other buffer, which still holds the frame from two flushes ago.
+    /// Whether this returns before the transfer ends depends on the
+    /// interface; use [`Self::is_flush_complete`] or [`Self::wait_flush`]
+    /// before the next call. `Lgt4s3Driver` returns while the last DMA chunk
+    /// is in flight; [`SpiInterface`] blocks like [`Self::flush`]. Requires
+    /// double buffering to be enabled.
+    pub fn flush_double_buffered(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let Some(back) = self.back_buffer.as_mut() else {
+            return Err(DriverError::InvalidConfiguration(
+                "Double buffering is not enabled",
+            ));
+        };
+        core::mem::swap(&mut self.framebuffer, back);
+
+        self.wait_flush()?;
+        self.restore_normal_display()?;
+        let (width, height) = self.dimensions();
+        self.set_window(0, 0, width - 1, height - 1)?;
+        if let Some(back) = self.back_buffer.as_ref() {
+            self.interface
+                .send_pixels_deferred(back)
+                .map_err(DriverError::InterfaceError)?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` once the last double-buffered flush has finished.
+    pub fn is_flush_complete(&mut self) -> bool {
+        self.interface.is_transfer_complete()
+    }
+
+    /// Blocks until the last double-buffered flush has finished.
+    pub fn wait_flush(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.interface
+            .wait_transfer_complete()
+            .map_err(DriverError::InterfaceError)
+    }
+
+    /// Sends a full frame from `data` to display RAM, bypassing the framebuffer.
+    ///
+    /// `data` must be laid out in the active [`ColorMode`] and be exactly one
+    /// frame long. It may live in flash, e.g. a `static` splash image, but note
+    /// that many DMA engines (including the ESP32-S3 GDMA) cannot read flash
+    /// directly. Interfaces that hand the slice to DMA as-is need it staged in
+    /// RAM first; interfaces that copy through their own DMA buffer, such as
+    /// `Lgt4s3Driver`, can stream it straight from flash.
+    pub fn flush_from(&mut self, data: &[u8]) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if data.len() != framebuffer_size(self.config, self.color_mode) {
+            return Err(DriverError::InvalidConfiguration(
+                "Frame data size does not match display size and color mode",
+            ));
+        }
+        let (width, height) = self.dimensions();
+        self.set_window(0, 0, width - 1, height - 1)?;
         self.interface
-            .send_pixels(&self.framebuffer)
+            .send_pixels(data)
             .map_err(DriverError::InterfaceError)?;
         Ok(())
     }
 
+    /// Writes pre-rendered pixel data straight to a region of the panel, bypassing the framebuffer.
+    ///
+    /// `data` must hold `size.width * size.height` pixels in the active
+    /// [`ColorMode`] byte layout, row-major. The region must fit on the
+    /// display and satisfy the alignment constraints enforced by
+    /// [`Self::set_window`]. The framebuffer is left untouched, so the next
+    /// full [`Self::flush`] overwrites the blitted pixels.
+    pub fn blit_raw(
+        &mut self,
+        top_left: Point,
+        size: Size,
+        data: &[u8],
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let expected =
+            size.width as usize * size.height as usize * self.color_mode.bytes_per_pixel();
+        if data.len() != expected {
+            return Err(DriverError::InvalidConfiguration(
+                "Blit data size does not match region size and color mode",
+            ));
+        }
+        let (width, height) = self.dimensions();
+        if size.width == 0
+            || size.height == 0
+            || top_left.x < 0
+            || top_left.y < 0
+            || top_left.x as u32 + size.width > width as u32
+            || top_left.y as u32 + size.height > height as u32
+        {
+            return Err(DriverError::InvalidConfiguration(
+                "Blit region lies outside the display",
+            ));
+        }
+        let (x_start, y_start) = (top_left.x as u16, top_left.y as u16);
+        self.set_window(
+            x_start,
+            y_start,
+            x_start + size.width as u16 - 1,
+            y_start + size.height as u16 - 1,
+        )?;
+        self.interface
+            .send_pixels(data)
+            .map_err(DriverError::InterfaceError)
+    }
+
+    /// Loads a full-frame RGB888 image into the framebuffer, converting it to the active [`ColorMode`].
+    ///
+    /// `rgb888` must hold exactly `width * height * 3` bytes in R, G, B order.
+    /// The framebuffer is only updated; call [`Self::flush`] to show it.
+    pub fn load_and_convert(
+        &mut self,
+        rgb888: &[u8],
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if rgb888.len() != framebuffer_size(self.config, ColorMode::Rgb888) {
+            return Err(DriverError::InvalidConfiguration(
+                "Source image size does not match display size",
+            ));
+        }
+
+        let encoder = self.pixel_encoder();
+        let bytes_per_pixel = self.color_mode.bytes_per_pixel();
+        for (src, dst) in rgb888
+            .chunks_exact(3)
+            .zip(self.framebuffer.chunks_exact_mut(bytes_per_pixel))
+        {
+            encoder.encode(Rgb888::new(src[0], src[1], src[2]), dst);
+        }
+        self.mark_all_dirty();
+        Ok(())
+    }
+
+    /// Renders one line of RGB565 pixels, as pushed by line-based GUI renderers
+    /// such as Slint's MCU line-buffer callback.
+    ///
+    /// `pixels` covers `x_range` on row `y` and is converted to the active
+    /// [`ColorMode`]. The RM690B0 only accepts windows with an even origin and
+    /// size, so a single row cannot be addressed on its own: the line is
+    /// written into the framebuffer and the enclosing even-aligned two-row
+    /// band is sent to the panel. Rows rendered earlier keep their content,
+    /// so the framebuffer must still be allocated. If the band transfer
+    /// fails, the line is marked dirty so a later [`Self::flush_dirty`] or
+    /// [`Self::flush_dirty_rows`] resends it. If it succeeds, the line is
+    /// not tracked as dirty; when the band spans the full width, earlier
+    /// changes on its rows were sent too and their rows are cleared.
+    pub fn render_line(
+        &mut self,
+        y: u16,
+        x_range: Range<u16>,
+        pixels: &[Rgb565],
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let (width, height) = self.dimensions();
+        if x_range.start >= x_range.end || x_range.end > width || y >= height {
+            return Err(DriverError::InvalidConfiguration(
+                "Line is outside the display bounds",
+            ));
+        }
+        if pixels.len() != (x_range.end - x_range.start) as usize {
+            return Err(DriverError::InvalidConfiguration(
+                "Line pixel count does not match x range",
+            ));
+        }
+
+        let encoder = self.pixel_encoder();
+        let bytes_per_pixel = self.color_mode.bytes_per_pixel();
+        let row_offset = y as usize * width as usize * bytes_per_pixel;
+        for (i, pixel) in pixels.iter().enumerate() {
+            let index = row_offset + (x_range.start as usize + i) * bytes_per_pixel;
+            encoder.encode(
+                (*pixel).into(),
+                &mut self.framebuffer[index..index + bytes_per_pixel],
+            );
+        }
+
+        let x_start = x_range.start & !1;
+        let x_end = ((x_range.end + 1) & !1) - 1;
+        let y_start = y & !1;
+        let result = self.flush_bounds(x_start, y_start, x_end, y_start + 1);
+        if result.is_err() {
+            self.mark_dirty(x_range.start, y, x_range.end - 1, y);
+        } else if x_start == 0 && x_end == width - 1 {
+            // Whole rows were sent, so nothing drawn on them is left to flush.
+            self.clear_row_dirty(y_start);
+            self.clear_row_dirty(y_start + 1);
+            if self.dirty_row_count() == 0 {
+                self.dirty = None;
+            }
+        }
+        result
+    }
+
+    /// Caps the refresh rate used by [`Self::flush_paced`].
+    ///
+    /// Passing `0` removes the limit.
+    pub fn set_max_fps(&mut self, fps: u8) {
+        self.min_frame_interval_us = if fps == 0 { 0 } else { 1_000_000 / fps as u64 };
+    }
+
+    /// Flushes the framebuffer only if enough time has passed since the last paced flush.
+    ///
+    /// `now` must return a monotonic timestamp in microseconds. Returns `Ok(true)`
+    /// if the frame was sent and `Ok(false)` if it was skipped to honour the
+    /// limit set with [`Self::set_max_fps`].
+    pub fn flush_paced(
+        &mut self,
+        now: impl Fn() -> u64,
+    ) -> Result<bool, DriverError<IFACE::Error, RST::Error>> {
+        let timestamp = now();
+        if let Some(last) = self.last_flush_us {
+            if timestamp.saturating_sub(last) < self.min_frame_interval_us {
+                return Ok(false);
+            }
+        }
+        self.flush()?;
+        self.last_flush_us = Some(timestamp);
+        Ok(true)
+    }
+
+    /// Flushes several regions, merging those that overlap or touch.
+    ///
+    /// Regions are in framebuffer coordinates, clipped to the display and
+    /// widened to even boundaries. Regions that overlap or share an edge
+    /// after widening are merged into their bounding box, repeatedly, and
+    /// each merged region is sent with one windowed transfer. Regions far
+    /// apart stay separate, so small updates in opposite corners don't pull
+    /// in the whole screen.
+    #[cfg(feature = "alloc")]
+    pub fn flush_regions(
+        &mut self,
+        regions: &[Rectangle],
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let (width, height) = self.dimensions();
+        let display = Rectangle::new(Point::zero(), Size::new(width as u32, height as u32));
+        let mut merged: Vec<(u16, u16, u16, u16)> = regions
+            .iter()
+            .filter_map(|region| {
+                let clipped = region.intersection(&display);
+                let bottom_right = clipped.bottom_right()?;
+                Some((
+                    clipped.top_left.x as u16 & !1,
+                    clipped.top_left.y as u16 & !1,
+                    (bottom_right.x as u16 | 1).min(width - 1),
+                    (bottom_right.y as u16 | 1).min(height - 1),
+                ))
+            })
+            .collect();
+
+        let touches = |a: (u16, u16, u16, u16), b: (u16, u16, u16, u16)| {
+            a.0 <= b.2 + 1 && b.0 <= a.2 + 1 && a.1 <= b.3 + 1 && b.1 <= a.3 + 1
+        };
+        'merge: loop {
+            for i in 0..merged.len() {
+                for j in i + 1..merged.len() {
+                    if touches(merged[i], merged[j]) {
+                        let (a, b) = (merged[i], merged.swap_remove(j));
+                        merged[i] = (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3));
+                        continue 'merge;
+                    }
+                }
+            }
+            break;
+        }
+
+        for (x_start, y_start, x_end, y_end) in merged {
+            self.flush_bounds(x_start, y_start, x_end, y_end)?;
+        }
+        Ok(())
+    }
+
+    /// Flushes a rectangular region from the framebuffer to display RAM.
+    ///
+    /// `area` is in logical display coordinates and must lie within the
+    /// display and satisfy the alignment constraints enforced by
+    /// [`Self::set_window`].
+    pub fn partial_flush_area(
+        &mut self,
+        area: &Rectangle,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        let (width, height) = self.dimensions();
+        let Some(bottom_right) = area.bottom_right() else {
+            return Err(DriverError::InvalidConfiguration("Flush area is empty"));
+        };
+        if area.top_left.x < 0
+            || area.top_left.y < 0
+            || bottom_right.x >= width as i32
+            || bottom_right.y >= height as i32
+        {
+            return Err(DriverError::InvalidConfiguration(
+                "Flush area lies outside the display",
+            ));
+        }
+        self.flush_bounds(
+            area.top_left.x as u16,
+            area.top_left.y as u16,
+            bottom_right.x as u16,
+            bottom_right.y as u16,
+        )
+    }
+
     /// Flushes a rectangular region from the framebuffer to display RAM.
     ///
     /// The region bounds are inclusive and must satisfy the alignment
     /// constraints enforced by [`Self::set_window`].
+    #[deprecated(
+        since = "0.1.3",
+        note = "use `partial_flush_area`, which takes a `Rectangle`"
+    )]
     pub fn partial_flush(
         &mut self,
         x_start: u16,
@@ -532,9 +3334,25 @@ where
         y_start: u16,
         y_end: u16,
     ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.flush_bounds(x_start, y_start, x_end, y_end)
+    }
+
+    /// Sends the framebuffer rows within the inclusive bounds, in [`Self::set_window`] order.
+    pub(crate) fn flush_bounds(
+        &mut self,
+        x_start: u16,
+        y_start: u16,
+        x_end: u16,
+        y_end: u16,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        if x_end < x_start || y_end < y_start {
+            return Err(DriverError::InvalidConfiguration(
+                "Flush region end must not be before its start",
+            ));
+        }
         self.set_window(x_start, y_start, x_end, y_end)?;
         let bytes_per_pixel = self.color_mode.bytes_per_pixel();
-        let fb_width = self.config.width as usize * bytes_per_pixel;
+        let fb_width = self.dimensions().0 as usize * bytes_per_pixel;
         let width = (x_end - x_start + 1) as usize;
         let height = (y_end - y_start + 1) as usize;
         for y in 0..height {
@@ -560,3 +3378,165 @@ where
         Ok(())
     }
 }
+
+/// Accessors for the driver's cached state, used by host-side tests.
+#[cfg(any(test, feature = "test-internals"))]
+impl<IFACE, RST, C> Rm690b0Driver<IFACE, RST, C>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    /// Returns the last brightness written with WRDISBV.
+    pub fn cached_brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Returns the last value written to MADCTR.
+    pub fn cached_madctr(&self) -> u8 {
+        self.madctr
+    }
+
+    /// Returns the last window passed to `set_window` as `(x_start, y_start, x_end, y_end)`.
+    pub fn current_window(&self) -> Option<(u16, u16, u16, u16)> {
+        self.window
+    }
+
+    /// Returns the panel rows last set with `set_partial_area`.
+    pub fn partial_area(&self) -> Option<(u16, u16)> {
+        self.partial_area
+    }
+
+    /// Returns the pixel shift applied to every window.
+    pub fn pixel_shift(&self) -> (i16, i16) {
+        self.pixel_shift
+    }
+}
+
+impl<IFACE, RST, C> Rm690b0Driver<IFACE, RST, C>
+where
+    IFACE: ControllerReadInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    /// Helper to read data bytes returned for a command
+    fn read_command(
+        &mut self,
+        cmd: u8,
+        buf: &mut [u8],
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.interface
+            .read_command(cmd, buf)
+            .map_err(DriverError::InterfaceError)
+    }
+
+    /// Reads the display identification bytes (RDDID).
+    ///
+    /// Useful to check that the panel booted and the bus is alive.
+    pub fn read_display_id(&mut self) -> Result<[u8; 3], DriverError<IFACE::Error, RST::Error>> {
+        let mut id = [0u8; 3];
+        self.read_command(commands::RDDID, &mut id)?;
+        Ok(id)
+    }
+
+    /// Reads and decodes the display identification (RDDID).
+    pub fn read_display_info(
+        &mut self,
+    ) -> Result<DisplayInfo, DriverError<IFACE::Error, RST::Error>> {
+        self.read_display_id().map(DisplayInfo::from_bytes)
+    }
+
+    /// Reads the number of interface errors detected by the panel (RDNUMED).
+    ///
+    /// Reading the counter clears it, so each call returns the errors seen
+    /// since the previous read. A count that keeps climbing points to a
+    /// marginal link, such as a long flex cable.
+    pub fn read_error_count(&mut self) -> Result<u8, DriverError<IFACE::Error, RST::Error>> {
+        let mut count = [0u8; 1];
+        self.read_command(commands::RDNUMED, &mut count)?;
+        Ok(count[0])
+    }
+
+    /// Reads the self-diagnostic result byte (RDDSDR).
+    pub fn read_self_diagnostic(&mut self) -> Result<u8, DriverError<IFACE::Error, RST::Error>> {
+        let mut result = [0u8; 1];
+        self.read_command(commands::RDDSDR, &mut result)?;
+        Ok(result[0])
+    }
+
+    /// Reads and decodes the display power mode (RDDPM).
+    pub fn read_power_mode(&mut self) -> Result<PowerMode, DriverError<IFACE::Error, RST::Error>> {
+        let mut bits = [0u8; 1];
+        self.read_command(commands::RDDPM, &mut bits)?;
+        Ok(PowerMode::from_bits(bits[0]))
+    }
+
+    /// Reads the interface pixel format the panel is using (RDDCOLMOD).
+    ///
+    /// After initialization this should match [`Self::color_mode`].
+    pub fn read_pixel_format(
+        &mut self,
+    ) -> Result<ColorMode, DriverError<IFACE::Error, RST::Error>> {
+        let mut colmod = [0u8; 1];
+        self.read_command(commands::RDDCOLMOD, &mut colmod)?;
+        ColorMode::from_colmod(colmod[0]).ok_or(DriverError::InvalidConfiguration(
+            "Panel reported an unknown pixel format",
+        ))
+    }
+
+    /// Reads the device descriptor block from its start (RDDDBS).
+    ///
+    /// The DDB is 5 bytes: a 2-byte supplier ID, 2 bytes of supplier
+    /// elective data identifying the panel revision, and an `0xFF` exit code.
+    /// A shorter `buf` reads only the leading bytes.
+    pub fn read_ddb(
+        &mut self,
+        buf: &mut [u8],
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.read_command(commands::RDDDBS, buf)
+    }
+
+    /// Continues reading the device descriptor block where the last read stopped (RDDDBC).
+    pub fn read_ddb_continue(
+        &mut self,
+        buf: &mut [u8],
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.read_command(commands::RDDDBC, buf)
+    }
+
+    /// Reads the frame checksums computed by the panel.
+    ///
+    /// The high byte is the first checksum (RDFCS) and the low byte the
+    /// continue checksum (RDCCS). Comparing them against a known-good frame
+    /// confirms the last transfer arrived uncorrupted.
+    pub fn read_checksum(&mut self) -> Result<u16, DriverError<IFACE::Error, RST::Error>> {
+        let mut first = [0u8; 1];
+        let mut cont = [0u8; 1];
+        self.read_command(commands::RDFCS, &mut first)?;
+        self.read_command(commands::RDCCS, &mut cont)?;
+        Ok(u16::from_be_bytes([first[0], cont[0]]))
+    }
+
+    /// Reads the scanline currently being refreshed by the panel (GSL).
+    pub fn get_scanline(&mut self) -> Result<u16, DriverError<IFACE::Error, RST::Error>> {
+        let mut line = [0u8; 2];
+        self.read_command(commands::GSL, &mut line)?;
+        Ok(u16::from_be_bytes(line))
+    }
+
+    /// Reads the display brightness the panel is using (RDDISBV).
+    ///
+    /// Compare with [`Self::brightness`] to confirm a brightness write took effect.
+    pub fn read_brightness(&mut self) -> Result<u8, DriverError<IFACE::Error, RST::Error>> {
+        let mut level = [0u8; 1];
+        self.read_command(commands::RDDISBV, &mut level)?;
+        Ok(level[0])
+    }
+
+    /// Reads the high brightness mode level (RDHBM).
+    pub fn read_hbm_brightness(&mut self) -> Result<u8, DriverError<IFACE::Error, RST::Error>> {
+        let mut level = [0u8; 1];
+        self.read_command(commands::RDHBM, &mut level)?;
+        Ok(level[0])
+    }
+}
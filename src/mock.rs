@@ -0,0 +1,189 @@
+//! Mock controller and reset interfaces for host-side testing.
+//!
+//! [`MockInterface`] records every transfer the driver makes so tests can
+//! assert on the exact command and pixel sequence, and [`MockReset`] counts
+//! hardware resets. Neither talks to real hardware.
+
+use crate::{ColorMode, ControllerInterface, ControllerReadInterface, ResetInterface};
+use alloc::vec::Vec;
+use core::convert::Infallible;
+
+/// A single transfer recorded by [`MockInterface`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recorded {
+    /// A command without parameters.
+    Command(u8),
+    /// A command followed by its parameter bytes.
+    CommandWithData(u8, Vec<u8>),
+    /// Pixel data written to display RAM.
+    Pixels(Vec<u8>),
+    /// A read command.
+    Read(u8),
+}
+
+/// Error injected by [`MockInterface::fail_pixels`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MockError;
+
+/// Controller interface that records transfers instead of sending them.
+///
+/// Reads return the bytes set with [`Self::respond`], or zeros. With the
+/// `async` feature it also implements `ControllerInterfaceAsync`, recording
+/// the same way.
+#[derive(Debug)]
+pub struct MockInterface {
+    /// Every transfer made through this interface, in order.
+    pub recorded: Vec<Recorded>,
+    /// Number of upcoming pixel transfers that fail with [`MockError`].
+    pub fail_pixels: usize,
+    /// Number of pixel transfers that succeed before `fail_pixels` applies.
+    pub pass_pixels: usize,
+    /// Color modes reported by [`ControllerInterface::supported_color_modes`].
+    pub supported: &'static [ColorMode],
+    responses: Vec<(u8, Vec<u8>)>,
+}
+
+impl Default for MockInterface {
+    fn default() -> Self {
+        MockInterface {
+            recorded: Vec::new(),
+            fail_pixels: 0,
+            pass_pixels: 0,
+            supported: &[
+                ColorMode::Rgb565,
+                ColorMode::Rgb888,
+                ColorMode::Rgb666,
+                ColorMode::Gray8,
+            ],
+            responses: Vec::new(),
+        }
+    }
+}
+
+impl MockInterface {
+    /// Creates a mock interface with an empty record.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clears the recorded transfers.
+    pub fn clear(&mut self) {
+        self.recorded.clear();
+    }
+
+    /// Sets the bytes returned when `cmd` is read.
+    pub fn respond(&mut self, cmd: u8, data: &[u8]) {
+        self.responses.retain(|(c, _)| *c != cmd);
+        self.responses.push((cmd, data.to_vec()));
+    }
+
+    /// Returns the parameters of every recorded `cmd`, in order.
+    pub fn data_of(&self, cmd: u8) -> Vec<&[u8]> {
+        self.recorded
+            .iter()
+            .filter_map(|r| match r {
+                Recorded::CommandWithData(c, data) if *c == cmd => Some(data.as_slice()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns every recorded pixel transfer joined into one byte stream.
+    pub fn pixel_bytes(&self) -> Vec<u8> {
+        self.recorded
+            .iter()
+            .filter_map(|r| match r {
+                Recorded::Pixels(bytes) => Some(bytes.as_slice()),
+                _ => None,
+            })
+            .flatten()
+            .copied()
+            .collect()
+    }
+}
+
+impl ControllerInterface for MockInterface {
+    type Error = MockError;
+
+    fn send_command(&mut self, cmd: u8) -> Result<(), Self::Error> {
+        self.recorded.push(Recorded::Command(cmd));
+        Ok(())
+    }
+
+    fn send_command_with_data(&mut self, cmd: u8, data: &[u8]) -> Result<(), Self::Error> {
+        self.recorded
+            .push(Recorded::CommandWithData(cmd, data.to_vec()));
+        Ok(())
+    }
+
+    fn send_pixels(&mut self, pixels: &[u8]) -> Result<(), Self::Error> {
+        if self.pass_pixels > 0 {
+            self.pass_pixels -= 1;
+        } else if self.fail_pixels > 0 {
+            self.fail_pixels -= 1;
+            return Err(MockError);
+        }
+        self.recorded.push(Recorded::Pixels(pixels.to_vec()));
+        Ok(())
+    }
+
+    fn supported_color_modes(&self) -> &'static [ColorMode] {
+        self.supported
+    }
+}
+
+impl ControllerReadInterface for MockInterface {
+    fn read_command(&mut self, cmd: u8, buf: &mut [u8]) -> Result<(), Self::Error> {
+        self.recorded.push(Recorded::Read(cmd));
+        buf.fill(0);
+        if let Some((_, data)) = self.responses.iter().find(|(c, _)| *c == cmd) {
+            let len = data.len().min(buf.len());
+            buf[..len].copy_from_slice(&data[..len]);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl crate::ControllerInterfaceAsync for MockInterface {
+    type Error = MockError;
+
+    async fn send_command(&mut self, cmd: u8) -> Result<(), Self::Error> {
+        ControllerInterface::send_command(self, cmd)
+    }
+
+    async fn send_command_with_data(&mut self, cmd: u8, data: &[u8]) -> Result<(), Self::Error> {
+        ControllerInterface::send_command_with_data(self, cmd, data)
+    }
+
+    async fn send_pixels(&mut self, pixels: &[u8]) -> Result<(), Self::Error> {
+        ControllerInterface::send_pixels(self, pixels)
+    }
+
+    fn supported_color_modes(&self) -> &'static [ColorMode] {
+        self.supported
+    }
+}
+
+/// Reset interface that counts resets.
+#[derive(Debug, Default)]
+pub struct MockReset {
+    /// Number of times [`ResetInterface::reset`] was called.
+    pub resets: usize,
+}
+
+impl MockReset {
+    /// Creates a mock reset with a zero count.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ResetInterface for MockReset {
+    type Error = Infallible;
+
+    fn reset(&mut self) -> Result<(), Self::Error> {
+        self.resets += 1;
+        Ok(())
+    }
+}
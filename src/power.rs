@@ -0,0 +1,94 @@
+//! Panel power-enable control.
+
+use crate::{PowerInterface, ResetInterface};
+use embedded_hal::digital::OutputPin;
+
+/// Power-enable through an active-high GPIO output pin.
+pub struct PowerPin<OUT> {
+    output: OUT,
+}
+
+impl<OUT> PowerPin<OUT>
+where
+    OUT: OutputPin,
+{
+    /// Creates a power control from the pin driving the panel's power-enable line.
+    pub fn new(output: OUT) -> Self {
+        PowerPin { output }
+    }
+
+    /// Releases the output pin.
+    pub fn release(self) -> OUT {
+        self.output
+    }
+}
+
+impl<OUT> PowerInterface for PowerPin<OUT>
+where
+    OUT: OutputPin,
+{
+    type Error = OUT::Error;
+
+    fn power_on(&mut self) -> Result<(), Self::Error> {
+        self.output.set_high()
+    }
+
+    fn power_off(&mut self) -> Result<(), Self::Error> {
+        self.output.set_low()
+    }
+}
+
+/// Errors from a [`PoweredReset`].
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PoweredResetError<PE, RE> {
+    /// Switching the power rail failed.
+    Power(PE),
+    /// The reset sequence failed.
+    Reset(RE),
+}
+
+/// Reset that enables the panel's power rail first.
+///
+/// Many AMOLED modules need their power rail up before reset is released.
+/// [`ResetInterface::reset`] switches the rail on, then runs the wrapped
+/// reset, so the driver's hard reset performs the whole power-up sequence.
+/// [`ResetInterface::power_off`] switches the rail off again, which
+/// `Rm690b0Driver::shutdown` does after putting the panel to sleep.
+pub struct PoweredReset<PWR, RST> {
+    power: PWR,
+    reset: RST,
+}
+
+impl<PWR, RST> PoweredReset<PWR, RST>
+where
+    PWR: PowerInterface,
+    RST: ResetInterface,
+{
+    /// Combines a power control and a reset.
+    pub fn new(power: PWR, reset: RST) -> Self {
+        PoweredReset { power, reset }
+    }
+
+    /// Releases the power control and reset.
+    pub fn release(self) -> (PWR, RST) {
+        (self.power, self.reset)
+    }
+}
+
+impl<PWR, RST> ResetInterface for PoweredReset<PWR, RST>
+where
+    PWR: PowerInterface,
+    RST: ResetInterface,
+{
+    type Error = PoweredResetError<PWR::Error, RST::Error>;
+
+    fn reset(&mut self) -> Result<(), Self::Error> {
+        self.power.power_on().map_err(PoweredResetError::Power)?;
+        self.reset.reset().map_err(PoweredResetError::Reset)
+    }
+
+    fn power_off(&mut self) -> Result<(), Self::Error> {
+        self.power.power_off().map_err(PoweredResetError::Power)
+    }
+}
@@ -0,0 +1,61 @@
+//! Reset through a GPIO output pin.
+
+use crate::ResetInterface;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+
+/// GPIO Reset Pin
+///
+/// Holds the reset line low for 20ms, then high for 150ms before the
+/// controller accepts commands. Panels that need a different pulse can
+/// change this with [`ResetDriver::with_timing`].
+pub struct ResetDriver<OUT, DELAY> {
+    output: OUT,
+    delay: DELAY,
+    low_ms: u32,
+    high_ms: u32,
+}
+
+impl<OUT, DELAY> ResetDriver<OUT, DELAY>
+where
+    OUT: OutputPin,
+    DELAY: DelayNs,
+{
+    /// Creates a new reset driver from a digital output pin and a delay.
+    pub fn new(output: OUT, delay: DELAY) -> Self {
+        ResetDriver {
+            output,
+            delay,
+            low_ms: 20,
+            high_ms: 150,
+        }
+    }
+
+    /// Sets how long the reset line is held low, then high, in milliseconds.
+    pub fn with_timing(mut self, low_ms: u32, high_ms: u32) -> Self {
+        self.low_ms = low_ms;
+        self.high_ms = high_ms;
+        self
+    }
+
+    /// Releases the output pin and delay.
+    pub fn release(self) -> (OUT, DELAY) {
+        (self.output, self.delay)
+    }
+}
+
+impl<OUT, DELAY> ResetInterface for ResetDriver<OUT, DELAY>
+where
+    OUT: OutputPin,
+    DELAY: DelayNs,
+{
+    type Error = OUT::Error;
+
+    fn reset(&mut self) -> Result<(), Self::Error> {
+        self.output.set_low()?;
+        self.delay.delay_ms(self.low_ms);
+        self.output.set_high()?;
+        self.delay.delay_ms(self.high_ms);
+        Ok(())
+    }
+}
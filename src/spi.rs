@@ -0,0 +1,97 @@
+//! Generic 4-wire SPI implementation of [`ControllerInterface`].
+//!
+//! For RM690B0 modules wired for single-data-line SPI with a data/command
+//! (D/C) pin instead of QSPI.
+
+use crate::{commands, ControllerInterface, ControllerReadInterface};
+use embedded_hal::digital::OutputPin;
+use embedded_hal::spi::{Operation, SpiDevice};
+
+/// Error returned by [`SpiInterface`].
+#[derive(Debug)]
+pub enum SpiInterfaceError<SpiE, DcE> {
+    /// The SPI transfer failed.
+    Spi(SpiE),
+    /// Driving the D/C pin failed.
+    Dc(DcE),
+}
+
+/// 4-wire SPI controller interface with a D/C pin.
+///
+/// Commands are sent with D/C low and their parameters and pixel data with
+/// D/C high. Chip select is handled by the [`SpiDevice`].
+pub struct SpiInterface<SPI, DC> {
+    spi: SPI,
+    dc: DC,
+}
+
+impl<SPI, DC> SpiInterface<SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    /// Creates a new SPI interface from an SPI device and a D/C pin.
+    pub fn new(spi: SPI, dc: DC) -> Self {
+        SpiInterface { spi, dc }
+    }
+
+    /// Releases the SPI device and D/C pin.
+    pub fn release(self) -> (SPI, DC) {
+        (self.spi, self.dc)
+    }
+
+    /// Writes `cmd` with D/C low.
+    fn write_command(&mut self, cmd: u8) -> Result<(), SpiInterfaceError<SPI::Error, DC::Error>> {
+        self.dc.set_low().map_err(SpiInterfaceError::Dc)?;
+        self.spi.write(&[cmd]).map_err(SpiInterfaceError::Spi)
+    }
+
+    /// Writes `bytes` with D/C high (parameters or pixel data).
+    fn write_data(&mut self, bytes: &[u8]) -> Result<(), SpiInterfaceError<SPI::Error, DC::Error>> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        self.dc.set_high().map_err(SpiInterfaceError::Dc)?;
+        self.spi.write(bytes).map_err(SpiInterfaceError::Spi)
+    }
+}
+
+impl<SPI, DC> ControllerInterface for SpiInterface<SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    type Error = SpiInterfaceError<SPI::Error, DC::Error>;
+
+    fn send_command(&mut self, cmd: u8) -> Result<(), Self::Error> {
+        self.write_command(cmd)
+    }
+
+    fn send_command_with_data(&mut self, cmd: u8, data: &[u8]) -> Result<(), Self::Error> {
+        self.write_command(cmd)?;
+        self.write_data(data)
+    }
+
+    fn send_pixels(&mut self, pixels: &[u8]) -> Result<(), Self::Error> {
+        self.write_command(commands::RAMWR)?;
+        self.write_data(pixels)
+    }
+
+    fn send_pixels_continue(&mut self, pixels: &[u8]) -> Result<(), Self::Error> {
+        self.write_command(commands::RAMWRC)?;
+        self.write_data(pixels)
+    }
+}
+
+impl<SPI, DC> ControllerReadInterface for SpiInterface<SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    fn read_command(&mut self, cmd: u8, buf: &mut [u8]) -> Result<(), Self::Error> {
+        self.dc.set_low().map_err(SpiInterfaceError::Dc)?;
+        self.spi
+            .transaction(&mut [Operation::Write(&[cmd]), Operation::Read(buf)])
+            .map_err(SpiInterfaceError::Spi)
+    }
+}
@@ -0,0 +1,112 @@
+//! Clipped and translated drawing into part of the framebuffer.
+
+use crate::{ControllerInterface, ResetInterface, Rm690b0Driver};
+use embedded_graphics::prelude::Transform;
+use embedded_graphics_core::pixelcolor::Rgb888;
+use embedded_graphics_core::prelude::*;
+use embedded_graphics_core::primitives::Rectangle;
+
+/// A `DrawTarget` covering one region of an [`Rm690b0Driver`]'s framebuffer.
+///
+/// Created with [`Rm690b0Driver::sub_view`]. Coordinates are relative to the
+/// region's top-left corner, drawing outside the region is discarded, and
+/// everything drawn updates the parent's dirty tracking.
+pub struct SubView<'a, IFACE, RST, C>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    parent: &'a mut Rm690b0Driver<IFACE, RST, C>,
+    area: Rectangle,
+}
+
+impl<IFACE, RST, C> Rm690b0Driver<IFACE, RST, C>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    /// Returns a `DrawTarget` limited to `area`, with its origin at the area's top-left corner.
+    ///
+    /// `area` is clipped to the display.
+    pub fn sub_view(&mut self, area: Rectangle) -> SubView<'_, IFACE, RST, C> {
+        let (width, height) = self.draw_dimensions();
+        let area = area.intersection(&Rectangle::new(
+            Point::zero(),
+            Size::new(width as u32, height as u32),
+        ));
+        SubView { parent: self, area }
+    }
+}
+
+impl<IFACE, RST, C> SubView<'_, IFACE, RST, C>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    /// Returns the region of the parent display this view covers.
+    pub fn area(&self) -> Rectangle {
+        self.area
+    }
+}
+
+impl<IFACE, RST, C> DrawTarget for SubView<'_, IFACE, RST, C>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor + Into<Rgb888>,
+{
+    type Color = C;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounds = self.bounding_box();
+        let offset = self.area.top_left;
+        self.parent.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(point, _)| bounds.contains(*point))
+                .map(|Pixel(point, color)| Pixel(point + offset, color)),
+        )
+    }
+
+    /// Forwards to the parent when `area` lies within the view, and draws pixel by pixel otherwise.
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        if area.intersection(&self.bounding_box()) == *area {
+            let area = area.translate(self.area.top_left);
+            self.parent.fill_contiguous(&area, colors)
+        } else {
+            self.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .map(|(point, color)| Pixel(point, color)),
+            )
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area
+            .intersection(&self.bounding_box())
+            .translate(self.area.top_left);
+        self.parent.fill_solid(&area, color)
+    }
+}
+
+impl<IFACE, RST, C> OriginDimensions for SubView<'_, IFACE, RST, C>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    fn size(&self) -> Size {
+        self.area.size
+    }
+}
@@ -0,0 +1,1803 @@
+//! Host-side tests of the driver against [`MockInterface`].
+
+use crate::mock::{MockInterface, MockReset, Recorded};
+use crate::*;
+use alloc::vec;
+use alloc::vec::Vec;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{Line, PrimitiveStyle};
+
+/// Delay that returns immediately.
+struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+type MockDriver<C> = Rm690b0Driver<MockInterface, MockReset, C>;
+
+/// Builds a `width` x `height` driver on the mock, with the initialization transfers cleared.
+fn driver<C: PixelColor, const N: usize>(
+    mode: ColorMode,
+    width: u16,
+    height: u16,
+) -> MockDriver<C> {
+    let mut driver = Rm690b0Builder::new(MockInterface::new(), MockReset::new())
+        .color_mode(mode)
+        .size(DisplaySize::new(width, height))
+        .build_heap::<_, N>(NoDelay)
+        .unwrap();
+    driver.interface.clear();
+    driver
+}
+
+/// Returns the recorded transfers.
+fn recorded<C: PixelColor>(driver: &MockDriver<C>) -> &[Recorded] {
+    &driver.interface.recorded
+}
+
+/// CASET or RASET parameters for an inclusive range.
+fn range(start: u16, end: u16) -> Vec<u8> {
+    let [s0, s1] = start.to_be_bytes();
+    let [e0, e1] = end.to_be_bytes();
+    vec![s0, s1, e0, e1]
+}
+
+#[test]
+fn draw_rectangle_and_flush_dirty_sends_window_and_rows() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    Rectangle::new(Point::new(2, 0), Size::new(2, 2))
+        .into_styled(PrimitiveStyle::with_fill(Rgb888::new(1, 2, 3)))
+        .draw(&mut driver)
+        .unwrap();
+    driver.flush_dirty().unwrap();
+
+    assert_eq!(
+        recorded(&driver),
+        &[
+            Recorded::CommandWithData(commands::CASET, range(2, 3)),
+            Recorded::CommandWithData(commands::RASET, range(0, 1)),
+            Recorded::Pixels(vec![1, 2, 3, 1, 2, 3]),
+            Recorded::Pixels(vec![1, 2, 3, 1, 2, 3]),
+        ]
+    );
+}
+
+#[test]
+fn flush_sends_full_window_then_framebuffer() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.flush().unwrap();
+
+    assert_eq!(
+        recorded(&driver),
+        &[
+            Recorded::CommandWithData(commands::CASET, range(0, 3)),
+            Recorded::CommandWithData(commands::RASET, range(0, 3)),
+            Recorded::Pixels(vec![0; 48]),
+        ]
+    );
+}
+
+#[test]
+fn build_resets_then_initializes() {
+    let driver = Rm690b0Builder::<_, _, Rgb888>::new(MockInterface::new(), MockReset::new())
+        .size(DisplaySize::new(4, 4))
+        .build_heap::<_, 48>(NoDelay)
+        .unwrap();
+
+    assert_eq!(driver.reset.resets, 1);
+    assert_eq!(recorded(&driver)[0], Recorded::Command(commands::SLPOUT));
+    assert_eq!(
+        recorded(&driver).last(),
+        Some(&Recorded::Command(commands::DISPON))
+    );
+}
+
+/// Checks that WRDISBV was written once, with `value`, right before DISPON.
+fn assert_brightness_set_before_dispon(recorded: &[Recorded], value: u8) {
+    let writes = recorded
+        .iter()
+        .filter(|transfer| matches!(transfer, Recorded::CommandWithData(commands::WRDISBV, _)))
+        .count();
+    assert_eq!(writes, 1);
+    assert_eq!(
+        recorded[recorded.len() - 2..],
+        [
+            Recorded::CommandWithData(commands::WRDISBV, vec![value]),
+            Recorded::Command(commands::DISPON),
+        ]
+    );
+}
+
+#[test]
+fn configured_brightness_is_written_once_before_dispon() {
+    let builder = || {
+        Rm690b0Builder::<_, _, Rgb888>::new(MockInterface::new(), MockReset::new())
+            .size(DisplaySize::new(4, 4))
+            .brightness(0x40)
+    };
+    let buffered = builder().build_heap::<_, 48>(NoDelay).unwrap();
+    let direct = builder().build_direct(NoDelay).unwrap();
+
+    assert_brightness_set_before_dispon(recorded(&buffered), 0x40);
+    assert_brightness_set_before_dispon(&direct.interface.recorded, 0x40);
+    assert_eq!(buffered.cached_brightness(), 0x40);
+}
+
+#[test]
+fn internal_getters_track_cached_state() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    assert_eq!(driver.cached_brightness(), 0xFF);
+    assert_eq!(driver.current_window(), None);
+
+    driver.set_brightness(0x40).unwrap();
+    driver.set_orientation(Orientation::Landscape).unwrap();
+    driver.set_window(0, 2, 3, 3).unwrap();
+    driver.set_partial_area(0, 1).unwrap();
+
+    assert_eq!(driver.cached_brightness(), 0x40);
+    assert_eq!(driver.cached_madctr(), Orientation::Landscape.madctr());
+    assert_eq!(driver.current_window(), Some((0, 2, 3, 3)));
+    assert_eq!(driver.partial_area(), Some((0, 1)));
+    assert_eq!(driver.pixel_shift(), (0, 0));
+}
+
+#[test]
+fn unsupported_color_mode_is_rejected_at_construction() {
+    let mut interface = MockInterface::new();
+    interface.supported = &[ColorMode::Rgb565];
+    let result = Rm690b0Builder::<_, _, Rgb888>::new(interface, MockReset::new())
+        .color_mode(ColorMode::Rgb888)
+        .size(DisplaySize::new(4, 4))
+        .build_heap::<_, 48>(NoDelay);
+
+    assert!(matches!(
+        result,
+        Err(DriverError::InvalidConfiguration(
+            "unsupported color mode for this panel"
+        ))
+    ));
+}
+
+#[test]
+fn unsupported_color_mode_is_rejected_at_runtime() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.interface.supported = &[ColorMode::Rgb888];
+
+    assert!(matches!(
+        driver.set_color_mode(ColorMode::Gray8),
+        Err(DriverError::InvalidConfiguration(_))
+    ));
+    assert!(recorded(&driver).is_empty());
+    assert_eq!(driver.color_mode(), ColorMode::Rgb888);
+}
+
+#[test]
+fn switching_rgb888_driver_to_rgb565_resizes_framebuffer() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.set_color_mode(ColorMode::Rgb565).unwrap();
+
+    assert_eq!(driver.color_mode(), ColorMode::Rgb565);
+    assert_eq!(driver.interface.data_of(commands::COLMOD), [[0x55]]);
+    assert_eq!(driver.framebuffer().len(), 4 * 4 * 2);
+}
+
+#[test]
+fn color_mode_defaults_to_native_wire_format() {
+    let driver = Rm690b0Builder::<_, _, Rgb565>::native(MockInterface::new(), MockReset::new())
+        .size(DisplaySize::new(4, 4))
+        .build_heap::<_, 32>(NoDelay)
+        .unwrap();
+
+    assert_eq!(driver.color_mode(), ColorMode::Rgb565);
+    assert_eq!(driver.interface.data_of(commands::COLMOD), [[0x55]]);
+}
+
+#[test]
+fn dirty_bounds_of_identical_frames_is_none() {
+    let frame = [7u8; 4 * 3 * 3];
+    assert_eq!(dirty_bounds(&frame, &frame, 4, 3), None);
+}
+
+#[test]
+fn dirty_bounds_of_single_pixel() {
+    let a = [0u8; 4 * 3 * 2];
+    let mut b = a;
+    // Pixel (2, 1) in a 4 pixel wide RGB565 frame.
+    b[(4 + 2) * 2 + 1] = 0xFF;
+    assert_eq!(
+        dirty_bounds(&a, &b, 4, 2),
+        Some(Rectangle::new(Point::new(2, 1), Size::new(1, 1)))
+    );
+}
+
+#[test]
+fn dirty_bounds_of_full_frame() {
+    let a = [0u8; 4 * 3];
+    let b = [1u8; 4 * 3];
+    assert_eq!(
+        dirty_bounds(&a, &b, 4, 1),
+        Some(Rectangle::new(Point::zero(), Size::new(4, 3)))
+    );
+}
+
+#[test]
+fn dirty_bounds_encloses_scattered_pixels() {
+    let a = [0u8; 6 * 5];
+    let mut b = a;
+    b[6 + 4] = 1; // (4, 1)
+    b[3 * 6 + 1] = 1; // (1, 3)
+    assert_eq!(
+        dirty_bounds(&a, &b, 6, 1),
+        Some(Rectangle::with_corners(Point::new(1, 1), Point::new(4, 3)))
+    );
+}
+
+#[test]
+fn dirty_bounds_compares_common_length_only() {
+    let a = [0u8; 8];
+    let b = [1u8; 4];
+    assert_eq!(
+        dirty_bounds(&a, &b, 2, 1),
+        Some(Rectangle::new(Point::zero(), Size::new(2, 2)))
+    );
+    assert_eq!(dirty_bounds(&a, &b, 0, 1), None);
+}
+
+#[test]
+fn render_line_sends_even_band_and_leaves_line_clean() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver
+        .render_line(3, 1..2, &[Rgb565::new(0x1F, 0, 0)])
+        .unwrap();
+
+    assert_eq!(
+        recorded(&driver),
+        &[
+            Recorded::CommandWithData(commands::CASET, range(0, 1)),
+            Recorded::CommandWithData(commands::RASET, range(2, 3)),
+            Recorded::Pixels(vec![0; 6]),
+            Recorded::Pixels(vec![0, 0, 0, 0xFF, 0, 0]),
+        ]
+    );
+    assert_eq!(driver.dirty_region(), None);
+    assert_eq!(driver.dirty_row_count(), 0);
+}
+
+#[test]
+fn render_line_full_width_clears_band_rows() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    Pixel(Point::new(3, 2), Rgb888::RED)
+        .draw(&mut driver)
+        .unwrap();
+    driver.render_line(3, 0..4, &[Rgb565::BLUE; 4]).unwrap();
+
+    assert_eq!(driver.dirty_region(), None);
+    assert_eq!(driver.dirty_row_count(), 0);
+}
+
+#[test]
+fn render_line_keeps_changes_outside_a_narrow_band() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    Pixel(Point::new(3, 3), Rgb888::RED)
+        .draw(&mut driver)
+        .unwrap();
+    driver.render_line(3, 1..2, &[Rgb565::BLUE]).unwrap();
+
+    assert_eq!(
+        driver.dirty_region(),
+        Some(Rectangle::new(Point::new(3, 3), Size::new(1, 1)))
+    );
+    assert_eq!(driver.dirty_row_count(), 1);
+}
+
+#[test]
+fn render_line_marks_line_dirty_when_send_fails() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.interface.fail_pixels = 1;
+    assert!(driver.render_line(1, 0..2, &[Rgb565::BLUE; 2]).is_err());
+
+    assert_eq!(
+        driver.dirty_region(),
+        Some(Rectangle::new(Point::new(0, 1), Size::new(2, 1)))
+    );
+    assert_eq!(driver.dirty_row_count(), 1);
+}
+
+/// Returns the CASET and RASET parameters of every recorded window.
+fn windows<C: PixelColor>(driver: &MockDriver<C>) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let caset = driver.interface.data_of(commands::CASET);
+    let raset = driver.interface.data_of(commands::RASET);
+    caset
+        .into_iter()
+        .zip(raset)
+        .map(|(c, r)| (c.to_vec(), r.to_vec()))
+        .collect()
+}
+
+#[test]
+fn flush_in_partial_mode_sends_only_active_rows() {
+    let mut driver = driver::<Rgb565, 48>(ColorMode::Rgb565, 4, 6);
+    driver.set_partial_area(2, 3).unwrap();
+    driver.enable_partial_mode().unwrap();
+    driver.interface.clear();
+    driver.flush().unwrap();
+
+    assert_eq!(windows(&driver), vec![(range(0, 3), range(2, 3))]);
+    assert_eq!(driver.interface.pixel_bytes().len(), 4 * 2 * 2);
+}
+
+#[test]
+fn partial_area_without_partial_mode_flushes_full_frame() {
+    let mut driver = driver::<Rgb565, 48>(ColorMode::Rgb565, 4, 6);
+    driver.set_partial_area(2, 3).unwrap();
+    driver.interface.clear();
+    driver.flush().unwrap();
+
+    assert_eq!(windows(&driver), vec![(range(0, 3), range(0, 5))]);
+    assert_eq!(driver.interface.pixel_bytes().len(), 48);
+}
+
+#[test]
+fn normal_mode_lifts_partial_flush_restriction() {
+    let mut driver = driver::<Rgb565, 48>(ColorMode::Rgb565, 4, 6);
+    driver.set_partial_area(2, 3).unwrap();
+    driver.enable_partial_mode().unwrap();
+    driver.enable_normal_mode().unwrap();
+    driver.interface.clear();
+    driver.flush().unwrap();
+
+    assert_eq!(windows(&driver), vec![(range(0, 3), range(0, 5))]);
+}
+
+#[test]
+fn flush_in_partial_mode_follows_orientation() {
+    let mut driver = driver::<Rgb565, 48>(ColorMode::Rgb565, 4, 6);
+    driver.set_partial_area(1, 2).unwrap();
+    driver.enable_partial_mode().unwrap();
+
+    driver
+        .set_orientation(Orientation::PortraitFlipped)
+        .unwrap();
+    driver.interface.clear();
+    driver.flush().unwrap();
+    assert_eq!(windows(&driver), vec![(range(0, 3), range(2, 5))]);
+
+    driver.set_orientation(Orientation::Landscape).unwrap();
+    driver.interface.clear();
+    driver.flush().unwrap();
+    assert_eq!(windows(&driver), vec![(range(0, 3), range(0, 3))]);
+    assert_eq!(driver.interface.pixel_bytes().len(), 4 * 4 * 2);
+
+    driver
+        .set_orientation(Orientation::LandscapeFlipped)
+        .unwrap();
+    driver.interface.clear();
+    driver.flush().unwrap();
+    assert_eq!(windows(&driver), vec![(range(2, 5), range(0, 3))]);
+}
+
+#[test]
+fn with_madctr_restores_previous_value_after_drawing() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    let previous = driver.cached_madctr();
+    driver
+        .with_madctr(madctr::MX, |driver| {
+            Pixel(Point::new(0, 0), Rgb888::WHITE).draw(driver).unwrap();
+            driver.flush()
+        })
+        .unwrap();
+
+    assert_eq!(
+        driver.interface.data_of(commands::MADCTR),
+        vec![&[madctr::MX][..], &[previous][..]]
+    );
+    assert!(driver
+        .interface
+        .pixel_bytes()
+        .starts_with(&[0xFF, 0xFF, 0xFF]));
+    assert_eq!(driver.cached_madctr(), previous);
+}
+
+#[test]
+fn with_madctr_restores_previous_value_when_closure_fails() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    let previous = driver.cached_madctr();
+    driver.interface.fail_pixels = 1;
+    let result = driver.with_madctr(madctr::MY, |driver| driver.flush());
+
+    assert!(matches!(result, Err(DriverError::InterfaceError(_))));
+    assert_eq!(
+        driver.interface.data_of(commands::MADCTR),
+        vec![&[madctr::MY][..], &[previous][..]]
+    );
+    assert_eq!(driver.cached_madctr(), previous);
+}
+
+/// Red, green, blue and white as a 2x2 RGB888 image.
+const RGB888_QUAD: [u8; 12] = [
+    0xFF, 0x00, 0x00, 0x00, 0xFF, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+];
+
+#[test]
+fn load_and_convert_encodes_rgb565() {
+    let mut driver = driver::<Rgb565, 8>(ColorMode::Rgb565, 2, 2);
+    driver.load_and_convert(&RGB888_QUAD).unwrap();
+    driver.flush().unwrap();
+
+    assert_eq!(
+        driver.interface.pixel_bytes(),
+        vec![0xF8, 0x00, 0x07, 0xE0, 0x00, 0x1F, 0xFF, 0xFF]
+    );
+}
+
+#[test]
+fn load_and_convert_encodes_gray8() {
+    let mut driver = driver::<Gray8, 4>(ColorMode::Gray8, 2, 2);
+    driver.load_and_convert(&RGB888_QUAD).unwrap();
+    driver.flush().unwrap();
+
+    assert_eq!(driver.interface.pixel_bytes(), vec![76, 149, 28, 255]);
+}
+
+#[test]
+fn load_and_convert_rejects_wrong_source_length() {
+    let mut driver = driver::<Rgb565, 8>(ColorMode::Rgb565, 2, 2);
+    assert!(matches!(
+        driver.load_and_convert(&RGB888_QUAD[..9]),
+        Err(DriverError::InvalidConfiguration(_))
+    ));
+}
+
+#[test]
+fn orientation_maps_logical_pixels_to_framebuffer_offsets() {
+    // Panel is 4 x 6; landscape variants swap the logical size. The
+    // framebuffer is laid out in logical coordinates, so logical (0, 0) is
+    // always offset 0 and (width - 1, 1) is offset 2 * width - 1.
+    let cases = [
+        (Orientation::Portrait, 0x00, (4, 6)),
+        (Orientation::Landscape, madctr::MV | madctr::MX, (6, 4)),
+        (
+            Orientation::PortraitFlipped,
+            madctr::MY | madctr::MX,
+            (4, 6),
+        ),
+        (
+            Orientation::LandscapeFlipped,
+            madctr::MV | madctr::MY,
+            (6, 4),
+        ),
+    ];
+    let mut driver = driver::<Gray8, 24>(ColorMode::Gray8, 4, 6);
+    for (orientation, madctr_bits, (width, height)) in cases {
+        driver.clear(Gray8::BLACK).unwrap();
+        driver.set_orientation(orientation).unwrap();
+        assert_eq!(driver.size(), Size::new(width, height));
+        assert_eq!(driver.cached_madctr(), madctr_bits);
+
+        driver
+            .draw_iter([
+                Pixel(Point::new(0, 0), Gray8::WHITE),
+                Pixel(Point::new(width as i32 - 1, 1), Gray8::WHITE),
+                Pixel(Point::new(width as i32, 0), Gray8::WHITE),
+            ])
+            .unwrap();
+        driver.interface.clear();
+        driver.flush().unwrap();
+
+        let pixels = driver.interface.pixel_bytes();
+        let lit: Vec<usize> = (0..pixels.len()).filter(|&i| pixels[i] != 0).collect();
+        assert_eq!(lit, vec![0, 2 * width as usize - 1], "{orientation:?}");
+        assert_eq!(
+            windows(&driver),
+            vec![(range(0, width as u16 - 1), range(0, height as u16 - 1))]
+        );
+    }
+}
+
+#[test]
+fn fill_solid_leaves_surrounding_pixels_untouched() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver
+        .fill_solid(
+            &Rectangle::new(Point::new(1, 1), Size::new(2, 2)),
+            Rgb888::new(1, 2, 3),
+        )
+        .unwrap();
+    driver.flush().unwrap();
+
+    let pixels = driver.interface.pixel_bytes();
+    for (i, pixel) in pixels.chunks_exact(3).enumerate() {
+        let (x, y) = (i % 4, i / 4);
+        let expected: &[u8] = if (1..3).contains(&x) && (1..3).contains(&y) {
+            &[1, 2, 3]
+        } else {
+            &[0, 0, 0]
+        };
+        assert_eq!(pixel, expected, "pixel ({x}, {y})");
+    }
+}
+
+#[test]
+fn fill_solid_clips_to_display() {
+    let mut driver = driver::<Gray8, 16>(ColorMode::Gray8, 4, 4);
+    driver
+        .fill_solid(
+            &Rectangle::new(Point::new(-2, 3), Size::new(4, 4)),
+            Gray8::WHITE,
+        )
+        .unwrap();
+    driver.flush().unwrap();
+
+    let mut expected = vec![0; 16];
+    expected[12..14].fill(0xFF);
+    assert_eq!(driver.interface.pixel_bytes(), expected);
+}
+
+#[test]
+fn fill_contiguous_off_the_bottom_right_stays_in_step() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    let consumed = core::cell::Cell::new(0);
+    let colors = (0..12u8)
+        .map(|i| Rgb888::new(i, i, i))
+        .inspect(|_| consumed.set(consumed.get() + 1));
+    driver
+        .fill_contiguous(&Rectangle::new(Point::new(2, 2), Size::new(3, 3)), colors)
+        .unwrap();
+
+    // Colors 2, 5 and 6..9 fall off the right and bottom edges.
+    let mut expected = vec![0; 48];
+    expected[30..36].copy_from_slice(&[0, 0, 0, 1, 1, 1]);
+    expected[42..48].copy_from_slice(&[3, 3, 3, 4, 4, 4]);
+    assert_eq!(driver.framebuffer(), expected);
+    assert_eq!(consumed.get(), 9);
+}
+
+/// Returns the fastest of five runs of `f`.
+fn best_of_five(mut f: impl FnMut()) -> std::time::Duration {
+    (0..5)
+        .map(|_| {
+            let start = std::time::Instant::now();
+            f();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+/// Builds a full-size 450 x 600 RGB565 driver for the timing comparisons.
+fn full_screen_driver() -> MockDriver<Rgb565> {
+    driver::<Rgb565, { 450 * 600 * 2 }>(ColorMode::Rgb565, 450, 600)
+}
+
+#[test]
+#[ignore = "timing comparison, run with `--release -- --ignored --nocapture`"]
+fn fill_contiguous_full_screen_blit_timing() {
+    // On an x86-64 host in release mode the override ran about three times
+    // faster than the per-pixel `draw_iter` path.
+    let mut driver = full_screen_driver();
+    let area = driver.bounding_box();
+    let image = || (0..450 * 600u32).map(|i| Rgb565::new(i as u8 & 0x1F, 0, 0));
+
+    let fast = best_of_five(|| driver.fill_contiguous(&area, image()).unwrap());
+    let default = best_of_five(|| {
+        driver
+            .draw_iter(area.points().zip(image()).map(|(p, c)| Pixel(p, c)))
+            .unwrap()
+    });
+    std::println!("fill_contiguous: {fast:?}, per-pixel draw_iter: {default:?}");
+}
+
+#[test]
+#[ignore = "timing comparison, run with `--release -- --ignored --nocapture`"]
+fn fill_solid_full_screen_timing() {
+    // Three to four times faster than `draw_iter` on an x86-64 host in release mode.
+    let mut driver = full_screen_driver();
+    let area = driver.bounding_box();
+
+    let fast = best_of_five(|| driver.fill_solid(&area, Rgb565::BLUE).unwrap());
+    let default = best_of_five(|| {
+        driver
+            .draw_iter(area.points().map(|p| Pixel(p, Rgb565::BLUE)))
+            .unwrap()
+    });
+    std::println!("fill_solid: {fast:?}, per-pixel draw_iter: {default:?}");
+}
+
+#[test]
+fn set_window_adds_ram_offsets() {
+    let mut driver = Rm690b0Builder::<_, _, Gray8>::new(MockInterface::new(), MockReset::new())
+        .color_mode(ColorMode::Gray8)
+        .size(DisplaySize::new(10, 10).with_offset(0, 4))
+        .col_offset(16)
+        .build_heap::<_, 100>(NoDelay)
+        .unwrap();
+    driver.interface.clear();
+    driver.set_window(0, 0, 9, 9).unwrap();
+
+    assert_eq!(
+        recorded(&driver),
+        &[
+            Recorded::CommandWithData(commands::CASET, range(16, 25)),
+            Recorded::CommandWithData(commands::RASET, range(4, 13)),
+        ]
+    );
+}
+
+#[test]
+fn set_inversion_sends_invon_and_invoff() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.set_inversion(true).unwrap();
+    assert!(driver.is_inverted());
+    driver.set_inversion(false).unwrap();
+    assert!(!driver.is_inverted());
+
+    assert_eq!(
+        recorded(&driver),
+        &[
+            Recorded::Command(commands::INVON),
+            Recorded::Command(commands::INVOFF),
+        ]
+    );
+}
+
+#[test]
+fn invert_colors_option_sends_invon_during_initialization() {
+    let driver = Rm690b0Builder::<_, _, Gray8>::new(MockInterface::new(), MockReset::new())
+        .color_mode(ColorMode::Gray8)
+        .size(DisplaySize::new(4, 4))
+        .invert_colors(true)
+        .build_heap::<_, 16>(NoDelay)
+        .unwrap();
+
+    assert!(driver.is_inverted());
+    assert!(recorded(&driver).contains(&Recorded::Command(commands::INVON)));
+}
+
+#[test]
+fn set_idle_mode_sends_idmon_and_idmoff() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.set_idle_mode(true).unwrap();
+    driver.set_idle_mode(false).unwrap();
+
+    assert_eq!(
+        recorded(&driver),
+        &[
+            Recorded::Command(commands::IDMON),
+            Recorded::Command(commands::IDMOFF),
+        ]
+    );
+    assert_eq!((commands::IDMON, commands::IDMOFF), (0x39, 0x38));
+}
+
+/// Delay that adds up the requested time instead of waiting.
+#[derive(Default)]
+struct TotalDelay {
+    ns: u64,
+}
+
+impl DelayNs for TotalDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.ns += ns as u64;
+    }
+}
+
+/// Returns the WRDISBV values written since the record was last cleared.
+fn brightness_writes<C: PixelColor>(driver: &MockDriver<C>) -> Vec<u8> {
+    driver
+        .interface
+        .data_of(commands::WRDISBV)
+        .into_iter()
+        .map(|data| data[0])
+        .collect()
+}
+
+#[test]
+fn fade_brightness_steps_monotonically_to_target() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.set_brightness(10).unwrap();
+    driver.interface.clear();
+    let mut delay = TotalDelay::default();
+    driver.fade_brightness(200, 100, &mut delay).unwrap();
+
+    let writes = brightness_writes(&driver);
+    assert_eq!(writes.len(), 25);
+    assert!(writes.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(writes.last(), Some(&200));
+    assert_eq!(driver.brightness(), 200);
+    assert_eq!(delay.ns, 24 * 4_000_000);
+
+    driver.interface.clear();
+    driver.fade_brightness(196, 100, &mut delay).unwrap();
+    assert_eq!(brightness_writes(&driver), vec![199, 198, 197, 196]);
+}
+
+/// Bus event seen by the SPI test doubles.
+#[derive(Debug, PartialEq, Eq)]
+enum SpiEvent {
+    Dc(bool),
+    Write(Vec<u8>),
+}
+
+type SpiLog = alloc::rc::Rc<core::cell::RefCell<Vec<SpiEvent>>>;
+
+/// SPI device and D/C pin that append to a shared log.
+struct LogSpi(SpiLog);
+struct LogDc(SpiLog);
+
+impl embedded_hal::spi::ErrorType for LogSpi {
+    type Error = core::convert::Infallible;
+}
+
+impl embedded_hal::spi::SpiDevice for LogSpi {
+    fn transaction(
+        &mut self,
+        operations: &mut [embedded_hal::spi::Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        for op in operations {
+            if let embedded_hal::spi::Operation::Write(bytes) = op {
+                self.0.borrow_mut().push(SpiEvent::Write(bytes.to_vec()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl embedded_hal::digital::ErrorType for LogDc {
+    type Error = core::convert::Infallible;
+}
+
+impl embedded_hal::digital::OutputPin for LogDc {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.borrow_mut().push(SpiEvent::Dc(false));
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.borrow_mut().push(SpiEvent::Dc(true));
+        Ok(())
+    }
+}
+
+#[test]
+fn spi_interface_drives_dc_low_for_commands_and_high_for_data() {
+    let log = SpiLog::default();
+    let mut spi = SpiInterface::new(LogSpi(log.clone()), LogDc(log.clone()));
+    spi.send_command(commands::SLPOUT).unwrap();
+    spi.send_command_with_data(commands::WRDISBV, &[0x80])
+        .unwrap();
+    spi.send_pixels(&[1, 2]).unwrap();
+    spi.send_pixels_continue(&[3]).unwrap();
+
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            SpiEvent::Dc(false),
+            SpiEvent::Write(vec![commands::SLPOUT]),
+            SpiEvent::Dc(false),
+            SpiEvent::Write(vec![commands::WRDISBV]),
+            SpiEvent::Dc(true),
+            SpiEvent::Write(vec![0x80]),
+            SpiEvent::Dc(false),
+            SpiEvent::Write(vec![commands::RAMWR]),
+            SpiEvent::Dc(true),
+            SpiEvent::Write(vec![1, 2]),
+            SpiEvent::Dc(false),
+            SpiEvent::Write(vec![commands::RAMWRC]),
+            SpiEvent::Dc(true),
+            SpiEvent::Write(vec![3]),
+        ]
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn partial_flush_rejects_inverted_bounds_without_sending() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    assert!(matches!(
+        driver.partial_flush(3, 0, 0, 1),
+        Err(DriverError::InvalidConfiguration(_))
+    ));
+    assert!(matches!(
+        driver.partial_flush(0, 1, 3, 0),
+        Err(DriverError::InvalidConfiguration(_))
+    ));
+    assert!(recorded(&driver).is_empty());
+}
+
+#[test]
+#[allow(deprecated)]
+fn partial_flush_single_pixel_region() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    // A lone pixel has an odd width and height, which the RM690B0 window
+    // alignment rejects; it must fail cleanly rather than underflow.
+    assert!(matches!(
+        driver.partial_flush(1, 1, 1, 1),
+        Err(DriverError::InvalidConfiguration(_))
+    ));
+    assert!(recorded(&driver).is_empty());
+
+    driver.partial_flush(2, 3, 2, 3).unwrap();
+    assert_eq!(windows(&driver), vec![(range(2, 3), range(2, 3))]);
+    assert_eq!(driver.interface.pixel_bytes().len(), 2 * 2 * 3);
+}
+
+#[test]
+fn flush_regions_merges_overlapping_rects() {
+    let mut driver = driver::<Rgb565, 512>(ColorMode::Rgb565, 16, 16);
+    driver
+        .flush_regions(&[
+            Rectangle::new(Point::new(1, 1), Size::new(4, 4)),
+            Rectangle::new(Point::new(3, 3), Size::new(4, 4)),
+        ])
+        .unwrap();
+
+    assert_eq!(windows(&driver), vec![(range(0, 7), range(0, 7))]);
+}
+
+#[test]
+fn flush_regions_keeps_disjoint_rects_separate() {
+    let mut driver = driver::<Rgb565, 512>(ColorMode::Rgb565, 16, 16);
+    driver
+        .flush_regions(&[
+            Rectangle::new(Point::new(0, 0), Size::new(2, 2)),
+            Rectangle::new(Point::new(12, 12), Size::new(2, 2)),
+        ])
+        .unwrap();
+
+    assert_eq!(
+        windows(&driver),
+        vec![(range(0, 1), range(0, 1)), (range(12, 13), range(12, 13))]
+    );
+}
+
+#[test]
+fn flush_regions_clamps_rects_to_the_display() {
+    let mut driver = driver::<Rgb565, 512>(ColorMode::Rgb565, 16, 16);
+    driver
+        .flush_regions(&[
+            Rectangle::new(Point::new(-3, 10), Size::new(6, 20)),
+            Rectangle::new(Point::new(20, 20), Size::new(4, 4)),
+        ])
+        .unwrap();
+
+    // The second rect lies entirely off-screen and is dropped.
+    assert_eq!(windows(&driver), vec![(range(0, 3), range(10, 15))]);
+}
+
+#[test]
+fn flush_regions_of_empty_slice_sends_nothing() {
+    let mut driver = driver::<Rgb565, 512>(ColorMode::Rgb565, 16, 16);
+    driver.flush_regions(&[]).unwrap();
+
+    assert!(recorded(&driver).is_empty());
+}
+
+/// Builds a 2 x 300 Gray8 driver, tall enough for row numbers above 255.
+fn tall_driver() -> MockDriver<Gray8> {
+    driver::<Gray8, 600>(ColorMode::Gray8, 2, 300)
+}
+
+#[test]
+fn set_partial_area_sends_big_endian_rows() {
+    let mut driver = tall_driver();
+    driver.set_partial_area(1, 260).unwrap();
+
+    assert_eq!(driver.interface.data_of(commands::PTLAR), [range(1, 260)]);
+}
+
+#[test]
+fn set_scroll_area_sends_big_endian_heights() {
+    let mut driver = tall_driver();
+    driver.set_scroll_area(10, 270, 20).unwrap();
+
+    assert_eq!(
+        driver.interface.data_of(commands::VSCRDEF),
+        [[0, 10, 1, 14, 0, 20]]
+    );
+}
+
+#[test]
+fn set_tear_scanline_sends_big_endian_line() {
+    let mut driver = tall_driver();
+    driver.set_tear_scanline(290).unwrap();
+
+    assert_eq!(driver.interface.data_of(commands::STESL), [[0x01, 0x22]]);
+}
+
+#[test]
+fn read_error_count_returns_rdnumed_value() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.interface.respond(commands::RDNUMED, &[7]);
+
+    assert_eq!(driver.read_error_count().unwrap(), 7);
+    assert_eq!(recorded(&driver), &[Recorded::Read(0x05)]);
+}
+
+#[test]
+fn software_rotation_draws_logical_rows_as_framebuffer_columns() {
+    for (rotation, column) in [
+        (SoftwareRotation::Rotate90, 3),
+        (SoftwareRotation::Rotate270, 0),
+    ] {
+        let mut driver = driver::<Gray8, 24>(ColorMode::Gray8, 4, 6);
+        driver.set_software_rotation(rotation);
+        assert_eq!(driver.size(), Size::new(6, 4));
+
+        Line::new(Point::new(0, 0), Point::new(5, 0))
+            .into_styled(PrimitiveStyle::with_stroke(Gray8::WHITE, 1))
+            .draw(&mut driver)
+            .unwrap();
+        driver.flush().unwrap();
+
+        assert!(driver.interface.data_of(commands::MADCTR).is_empty());
+        assert_eq!(windows(&driver), vec![(range(0, 3), range(0, 5))]);
+        let pixels = driver.interface.pixel_bytes();
+        let lit: Vec<usize> = (0..pixels.len()).filter(|&i| pixels[i] != 0).collect();
+        let expected: Vec<usize> = (0..6).map(|y| y * 4 + column).collect();
+        assert_eq!(lit, expected, "{rotation:?}");
+    }
+}
+
+#[test]
+fn set_bgr_swaps_red_and_blue_in_every_framebuffer_writer() {
+    let mut driver = driver::<Rgb888, 12>(ColorMode::Rgb888, 2, 2);
+    driver.set_bgr(true);
+
+    Pixel(Point::new(0, 0), Rgb888::RED)
+        .draw(&mut driver)
+        .unwrap();
+    driver.flush().unwrap();
+    assert_eq!(driver.interface.pixel_bytes()[..3], [0x00, 0x00, 0xFF]);
+
+    driver.load_and_convert(&RGB888_QUAD).unwrap();
+    driver.interface.clear();
+    driver.flush().unwrap();
+    assert_eq!(driver.interface.pixel_bytes()[..3], [0x00, 0x00, 0xFF]);
+
+    driver.interface.clear();
+    driver
+        .render_line(1, 0..1, &[Rgb565::new(0x1F, 0, 0)])
+        .unwrap();
+    assert_eq!(driver.interface.pixel_bytes()[6..9], [0x00, 0x00, 0xFF]);
+}
+
+#[test]
+fn rgb565_endianness_orders_bytes_in_every_framebuffer_writer() {
+    for (endianness, expected) in [
+        (Endianness::Big, [0xF8, 0x00]),
+        (Endianness::Little, [0x00, 0xF8]),
+    ] {
+        let mut driver = driver::<Rgb565, 8>(ColorMode::Rgb565, 2, 2);
+        driver.set_rgb565_endianness(endianness);
+
+        Pixel(Point::new(0, 0), Rgb565::RED)
+            .draw(&mut driver)
+            .unwrap();
+        driver.flush().unwrap();
+        assert_eq!(driver.interface.pixel_bytes()[..2], expected);
+
+        driver.load_and_convert(&RGB888_QUAD).unwrap();
+        driver.interface.clear();
+        driver.flush().unwrap();
+        assert_eq!(driver.interface.pixel_bytes()[..2], expected);
+
+        driver.interface.clear();
+        driver.render_line(1, 0..1, &[Rgb565::RED]).unwrap();
+        assert_eq!(driver.interface.pixel_bytes()[4..6], expected);
+    }
+}
+
+#[test]
+fn read_display_info_decodes_rddid_bytes() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver
+        .interface
+        .respond(commands::RDDID, &[0x01, 0x80, 0xB0]);
+
+    assert_eq!(
+        driver.read_display_info().unwrap(),
+        DisplayInfo {
+            manufacturer_id: 0x01,
+            version: 0x80,
+            driver_id: 0xB0,
+        }
+    );
+    assert_eq!(recorded(&driver), &[Recorded::Read(commands::RDDID)]);
+}
+
+/// Returns the tearing effect commands sent while building with `mode`.
+fn tear_commands(mode: Option<TearMode>) -> Vec<Recorded> {
+    let driver = Rm690b0Builder::<_, _, Gray8>::new(MockInterface::new(), MockReset::new())
+        .color_mode(ColorMode::Gray8)
+        .size(DisplaySize::new(4, 4))
+        .tear_mode(mode)
+        .build_heap::<_, 16>(NoDelay)
+        .unwrap();
+    recorded(&driver)
+        .iter()
+        .filter(|r| {
+            matches!(
+                r,
+                Recorded::Command(commands::TEON | commands::TEOFF)
+                    | Recorded::CommandWithData(commands::TEON | commands::TEOFF, _)
+            )
+        })
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn tear_mode_selects_teon_parameter_or_teoff() {
+    assert_eq!(
+        tear_commands(Some(TearMode::VBlankOnly)),
+        vec![Recorded::CommandWithData(commands::TEON, vec![0x00])]
+    );
+    assert_eq!(
+        tear_commands(Some(TearMode::VBlankAndHBlank)),
+        vec![Recorded::CommandWithData(commands::TEON, vec![0x01])]
+    );
+    assert_eq!(
+        tear_commands(None),
+        vec![Recorded::Command(commands::TEOFF)]
+    );
+}
+
+#[test]
+fn tearing_effect_can_be_changed_at_runtime() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver
+        .enable_tearing_effect(TearMode::VBlankAndHBlank)
+        .unwrap();
+    driver.disable_tearing_effect().unwrap();
+
+    assert_eq!(
+        recorded(&driver),
+        &[
+            Recorded::CommandWithData(commands::TEON, vec![0x01]),
+            Recorded::Command(commands::TEOFF),
+        ]
+    );
+    assert_eq!(driver.tear_mode(), None);
+}
+
+#[test]
+fn flush_with_retries_recovers_after_transient_failures() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.interface.fail_pixels = 2;
+    let mut delay = TotalDelay::default();
+    driver.flush_with_retries(3, &mut delay, 10).unwrap();
+
+    // Three attempts, each setting the window; only the last one sends pixels.
+    assert_eq!(driver.interface.data_of(commands::CASET).len(), 3);
+    assert_eq!(driver.interface.pixel_bytes().len(), 48);
+    assert_eq!(delay.ns, (10 + 20) * 1_000_000);
+}
+
+#[test]
+fn flush_with_retries_returns_last_error_when_all_attempts_fail() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.interface.fail_pixels = 3;
+    let mut delay = TotalDelay::default();
+    let result = driver.flush_with_retries(2, &mut delay, 10);
+
+    assert!(matches!(result, Err(DriverError::InterfaceError(_))));
+    assert_eq!(driver.interface.data_of(commands::CASET).len(), 3);
+    assert!(driver.interface.pixel_bytes().is_empty());
+    assert_eq!(delay.ns, (10 + 20) * 1_000_000);
+}
+
+#[test]
+fn draw_blended_mixes_red_over_blue_at_half_alpha() {
+    let mut rgb888 = driver::<Rgb888, 12>(ColorMode::Rgb888, 2, 2);
+    rgb888.clear(Rgb888::BLUE).unwrap();
+    rgb888.draw_blended([Pixel(Point::new(0, 0), Rgb888::RED)], 0x80);
+    rgb888.flush().unwrap();
+    assert_eq!(
+        rgb888.interface.pixel_bytes()[..6],
+        [128, 0, 127, 0, 0, 255]
+    );
+
+    // RGB565 blends at the stored precision: 128 and 127 become 16 and 15.
+    let mut rgb565 = driver::<Rgb565, 8>(ColorMode::Rgb565, 2, 2);
+    rgb565.clear(Rgb565::BLUE).unwrap();
+    rgb565.draw_blended([Pixel(Point::new(0, 0), Rgb565::RED)], 0x80);
+    rgb565.flush().unwrap();
+    assert_eq!(
+        rgb565.interface.pixel_bytes()[..4],
+        [0x80, 0x0F, 0x00, 0x1F]
+    );
+}
+
+#[test]
+fn draw_iter_encodes_pixel_for_each_color_mode() {
+    let color = Rgb888::new(0x12, 0x34, 0x56);
+    let cases: [(ColorMode, &[u8]); 4] = [
+        (ColorMode::Rgb888, &[0x12, 0x34, 0x56]),
+        (ColorMode::Rgb666, &[0x10, 0x34, 0x54]),
+        (ColorMode::Rgb565, &[0x11, 0xAA]),
+        (ColorMode::Gray8, &[0x2D]),
+    ];
+    for (mode, expected) in cases {
+        let mut driver = driver::<Rgb888, 48>(mode, 4, 4 * 3 / mode.bytes_per_pixel() as u16);
+        Pixel(Point::new(1, 0), color).draw(&mut driver).unwrap();
+        driver.flush().unwrap();
+
+        let bytes_per_pixel = mode.bytes_per_pixel();
+        let pixels = driver.interface.pixel_bytes();
+        assert_eq!(pixels.len(), 48, "{mode:?}");
+        assert_eq!(
+            &pixels[..bytes_per_pixel],
+            vec![0; bytes_per_pixel],
+            "{mode:?}"
+        );
+        assert_eq!(
+            &pixels[bytes_per_pixel..2 * bytes_per_pixel],
+            expected,
+            "{mode:?}"
+        );
+    }
+}
+
+#[test]
+fn rgb565_driver_packs_red_big_endian() {
+    let mut driver = driver::<Rgb565, 32>(ColorMode::Rgb565, 4, 4);
+    assert_eq!(
+        framebuffer_size(DisplaySize::new(4, 4), ColorMode::Rgb565),
+        32
+    );
+    Pixel(Point::new(0, 0), Rgb565::RED)
+        .draw(&mut driver)
+        .unwrap();
+    driver.flush().unwrap();
+
+    assert_eq!(driver.interface.pixel_bytes()[..2], [0xF8, 0x00]);
+}
+
+#[test]
+fn set_window_accepts_last_column_and_rejects_width() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.set_window(2, 0, 3, 1).unwrap();
+    assert_eq!(windows(&driver), vec![(range(2, 3), range(0, 1))]);
+
+    driver.interface.clear();
+    assert!(matches!(
+        driver.set_window(2, 0, 4, 1),
+        Err(DriverError::InvalidConfiguration(_))
+    ));
+    assert!(matches!(
+        driver.set_window(4, 0, 5, 1),
+        Err(DriverError::InvalidConfiguration(_))
+    ));
+    assert!(recorded(&driver).is_empty());
+}
+
+#[test]
+fn set_window_rejects_offset_past_display_ram() {
+    let mut driver = Rm690b0Builder::<_, _, Gray8>::new(MockInterface::new(), MockReset::new())
+        .color_mode(ColorMode::Gray8)
+        .size(DisplaySize::new(4, 4))
+        .col_offset(MAX_COLUMNS - 2)
+        .build_heap::<_, 16>(NoDelay)
+        .unwrap();
+    driver.interface.clear();
+
+    driver.set_window(0, 0, 1, 1).unwrap();
+    assert_eq!(
+        windows(&driver),
+        vec![(range(MAX_COLUMNS - 2, MAX_COLUMNS - 1), range(0, 1))]
+    );
+    assert!(matches!(
+        driver.set_window(0, 0, 3, 1),
+        Err(DriverError::InvalidConfiguration(_))
+    ));
+}
+
+#[test]
+fn mismatched_framebuffer_length_is_rejected() {
+    let config = DisplaySize::new(4, 4);
+    let too_short = MockDriver::<Rgb888>::new_heap::<_, 47>(
+        MockInterface::new(),
+        MockReset::new(),
+        ColorMode::Rgb888,
+        config,
+        NoDelay,
+    );
+    assert!(matches!(
+        too_short,
+        Err(DriverError::InvalidConfiguration(_))
+    ));
+
+    let framebuffer = alloc::boxed::Box::leak(alloc::boxed::Box::new([0u8; 49]));
+    let too_long = MockDriver::<Rgb888>::new_static(
+        MockInterface::new(),
+        MockReset::new(),
+        ColorMode::Rgb888,
+        config,
+        NoDelay,
+        framebuffer,
+    );
+    assert!(matches!(
+        too_long,
+        Err(DriverError::InvalidConfiguration(_))
+    ));
+}
+
+#[test]
+fn rgb666_keeps_upper_six_bits_of_each_channel() {
+    let mid_gray = Rgb888::new(0x7F, 0x7F, 0x7F);
+    let mut driver = driver::<Rgb888, 12>(ColorMode::Rgb666, 2, 2);
+    Pixel(Point::new(0, 0), mid_gray).draw(&mut driver).unwrap();
+    driver
+        .fill_solid(&Rectangle::new(Point::new(0, 1), Size::new(2, 1)), mid_gray)
+        .unwrap();
+    driver.flush().unwrap();
+
+    assert_eq!(
+        driver.interface.pixel_bytes(),
+        vec![0x7C, 0x7C, 0x7C, 0, 0, 0, 0x7C, 0x7C, 0x7C, 0x7C, 0x7C, 0x7C]
+    );
+}
+
+#[test]
+fn gray8_stores_white_as_single_full_byte() {
+    let mut driver = driver::<Gray8, 16>(ColorMode::Gray8, 4, 4);
+    Pixel(Point::new(1, 0), Gray8::WHITE)
+        .draw(&mut driver)
+        .unwrap();
+    driver.flush().unwrap();
+
+    let mut expected = vec![0; 16];
+    expected[1] = 0xFF;
+    assert_eq!(driver.interface.pixel_bytes(), expected);
+}
+
+/// A gamma curve with distinct positive and negative coefficients.
+fn test_gamma_curve() -> GammaCurve {
+    let mut positive = [0; GAMMA_POINTS];
+    let mut negative = [0; GAMMA_POINTS];
+    for i in 0..GAMMA_POINTS {
+        positive[i] = i as u8;
+        negative[i] = 0x80 | i as u8;
+    }
+    GammaCurve {
+        page: 0x50,
+        positive_register: 0x00,
+        negative_register: 0x20,
+        positive,
+        negative,
+    }
+}
+
+#[test]
+fn set_gamma_brackets_writes_with_page_switches() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    let curve = test_gamma_curve();
+    driver.set_gamma(&curve).unwrap();
+
+    let mut expected = vec![Recorded::CommandWithData(commands::CMD_PAGE, vec![0x50])];
+    for i in 0..GAMMA_POINTS as u8 {
+        expected.push(Recorded::CommandWithData(i, vec![i]));
+    }
+    for i in 0..GAMMA_POINTS as u8 {
+        expected.push(Recorded::CommandWithData(0x20 + i, vec![0x80 | i]));
+    }
+    expected.push(Recorded::CommandWithData(commands::CMD_PAGE, vec![0x00]));
+    assert_eq!(recorded(&driver), expected.as_slice());
+}
+
+#[test]
+fn set_gamma_rejects_registers_past_0xff() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    let curve = GammaCurve {
+        negative_register: 0xF1,
+        ..test_gamma_curve()
+    };
+
+    assert!(matches!(
+        driver.set_gamma(&curve),
+        Err(DriverError::InvalidConfiguration(_))
+    ));
+    assert!(recorded(&driver).is_empty());
+
+    let curve = GammaCurve {
+        negative_register: 0xF0,
+        ..test_gamma_curve()
+    };
+    driver.set_gamma(&curve).unwrap();
+    assert_eq!(
+        recorded(&driver)[2 * GAMMA_POINTS],
+        Recorded::CommandWithData(0xFF, vec![0x8F])
+    );
+}
+
+#[test]
+fn srgb_default_gamma_is_monotonic_and_uploads() {
+    let curve = GammaCurve::srgb_default();
+    assert!(curve.positive.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(
+        (curve.positive[0], curve.positive[GAMMA_POINTS - 1]),
+        (0, 255)
+    );
+    assert_eq!(curve.negative, curve.positive);
+
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.set_gamma(&curve).unwrap();
+    assert_eq!(
+        driver.interface.data_of(commands::CMD_PAGE),
+        [[0x50], [0x00]]
+    );
+}
+
+#[test]
+fn set_rad_acl_writes_and_caches_register_0x55() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.set_rad_acl(0x21).unwrap();
+
+    assert_eq!(
+        recorded(&driver),
+        &[Recorded::CommandWithData(0x55, vec![0x21])]
+    );
+    assert_eq!(driver.rad_acl(), 0x21);
+
+    driver.soft_reset(&mut NoDelay).unwrap();
+    assert_eq!(driver.rad_acl(), 0x00);
+}
+
+#[test]
+fn color_temperature_and_acl_keep_each_others_bits() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.set_acl(AclLevel::Medium).unwrap();
+    driver.set_color_temperature(ColorTemp::Warm).unwrap();
+    driver.set_acl(AclLevel::High).unwrap();
+    driver.set_color_temperature(ColorTemp::Cool).unwrap();
+
+    assert_eq!(
+        driver.interface.data_of(commands::WRRADACL),
+        [[0x02], [0x12], [0x13], [0x23]]
+    );
+    assert_eq!(driver.rad_acl(), 0x23);
+}
+
+#[test]
+fn set_frame_rate_level_writes_and_caches_fr_level() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    assert_eq!(driver.frame_rate_level(), None);
+    driver.set_frame_rate_level(0x02).unwrap();
+
+    assert_eq!(
+        recorded(&driver),
+        &[Recorded::CommandWithData(commands::FR_LEVEL, vec![0x02])]
+    );
+    assert_eq!(driver.frame_rate_level(), Some(0x02));
+
+    driver.soft_reset(&mut NoDelay).unwrap();
+    assert_eq!(driver.frame_rate_level(), None);
+}
+
+#[test]
+fn set_frame_rate_writes_fr_level_and_tracks_rate() {
+    let mut driver = driver::<Rgb565, 32>(ColorMode::Rgb565, 4, 4);
+    assert_eq!(driver.frame_rate(), FrameRate::Hz60);
+    driver.set_frame_rate(FrameRate::Hz30).unwrap();
+    driver.set_frame_rate(FrameRate::Hz90).unwrap();
+
+    assert_eq!(
+        recorded(&driver),
+        &[
+            Recorded::CommandWithData(commands::FR_LEVEL, vec![0x00]),
+            Recorded::CommandWithData(commands::FR_LEVEL, vec![0x02]),
+        ]
+    );
+    assert_eq!(driver.frame_rate(), FrameRate::Hz90);
+}
+
+#[test]
+fn unsupported_frame_rate_and_color_mode_combinations_are_rejected() {
+    let mut rgb888 = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    assert!(matches!(
+        rgb888.set_frame_rate(FrameRate::Hz90),
+        Err(DriverError::InvalidConfiguration(_))
+    ));
+    assert!(recorded(&rgb888).is_empty());
+    assert_eq!(rgb888.frame_rate(), FrameRate::Hz60);
+
+    let mut rgb565 = driver::<Rgb565, 32>(ColorMode::Rgb565, 4, 4);
+    rgb565.set_frame_rate(FrameRate::Hz90).unwrap();
+    rgb565.interface.clear();
+    assert!(matches!(
+        rgb565.set_color_mode(ColorMode::Rgb888),
+        Err(DriverError::InvalidConfiguration(
+            "Frame rate is not supported in this color mode"
+        ))
+    ));
+    assert!(recorded(&rgb565).is_empty());
+    assert_eq!(rgb565.color_mode(), ColorMode::Rgb565);
+}
+
+#[test]
+fn set_pixel_format_option_sets_madctr_rgb_bit_only() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.set_orientation(Orientation::Landscape).unwrap();
+    driver.interface.clear();
+    driver
+        .set_pixel_format_option(PixelFormatOptions {
+            color_order: ColorOrder::Bgr,
+        })
+        .unwrap();
+
+    assert_eq!(
+        recorded(&driver),
+        &[Recorded::CommandWithData(
+            commands::MADCTR,
+            vec![madctr::MV | madctr::MX | madctr::RGB]
+        )]
+    );
+
+    driver.set_orientation(Orientation::Portrait).unwrap();
+    assert_eq!(driver.cached_madctr(), madctr::RGB);
+}
+
+#[test]
+fn hbm_mode_and_brightness_are_sent_as_given() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.set_hbm_mode(0x01).unwrap();
+    driver.set_hbm_brightness(0xC0).unwrap();
+    driver.interface.respond(commands::RDHBM, &[0xC0]);
+
+    assert_eq!(driver.read_hbm_brightness().unwrap(), 0xC0);
+    assert_eq!(
+        recorded(&driver),
+        &[
+            Recorded::CommandWithData(commands::HBM_MODE, vec![0x01]),
+            Recorded::CommandWithData(commands::WRHBM, vec![0xC0]),
+            Recorded::Read(commands::RDHBM),
+        ]
+    );
+}
+
+#[test]
+fn flush_converting_sends_rgb565_rows() {
+    let mut driver = driver::<Rgb888, 12>(ColorMode::Rgb888, 2, 2);
+    driver.load_and_convert(&RGB888_QUAD).unwrap();
+    driver.flush_converting(ColorMode::Rgb565).unwrap();
+
+    assert_eq!(
+        recorded(&driver),
+        &[
+            Recorded::CommandWithData(commands::CASET, range(0, 1)),
+            Recorded::CommandWithData(commands::RASET, range(0, 1)),
+            Recorded::Pixels(vec![0xF8, 0x00, 0x07, 0xE0]),
+            Recorded::Pixels(vec![0x00, 0x1F, 0xFF, 0xFF]),
+        ]
+    );
+}
+
+#[test]
+fn flush_converting_honours_bgr_endianness_and_partial_mode() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 2, 8);
+    driver.set_bgr(true);
+    driver.set_rgb565_endianness(Endianness::Little);
+    Pixel(Point::new(0, 2), Rgb888::RED)
+        .draw(&mut driver)
+        .unwrap();
+    driver.set_partial_area(2, 3).unwrap();
+    driver.enable_partial_mode().unwrap();
+    driver.interface.clear();
+    driver.flush_converting(ColorMode::Rgb565).unwrap();
+
+    assert_eq!(windows(&driver), vec![(range(0, 1), range(2, 3))]);
+    // Red with the channels swapped is pure blue, 0x001F, sent low byte first.
+    assert_eq!(
+        driver.interface.pixel_bytes(),
+        vec![0x1F, 0x00, 0, 0, 0, 0, 0, 0]
+    );
+}
+
+#[test]
+fn dirty_row_bitmap_is_sized_from_configured_panel() {
+    let small = driver::<Gray8, 16>(ColorMode::Gray8, 4, 4);
+    assert_eq!(small.dirty_rows.len(), 1);
+
+    // Taller than the controller's 600 RAM rows, so a bitmap sized for the
+    // RAM would drop the last rows.
+    let mut driver = driver::<Gray8, 1400>(ColorMode::Gray8, 2, 700);
+    assert_eq!(driver.dirty_rows.len(), 700usize.div_ceil(32));
+
+    Pixel(Point::new(0, 699), Gray8::WHITE)
+        .draw(&mut driver)
+        .unwrap();
+    assert_eq!(driver.dirty_row_count(), 1);
+}
+
+#[test]
+fn geometry_changes_restart_interrupted_resumable_flush() {
+    let mut driver = driver::<Gray8, 24>(ColorMode::Gray8, 4, 6);
+    driver.interface.pass_pixels = 2;
+    driver.interface.fail_pixels = 1;
+    assert!(driver.flush_resumable(2).is_err());
+    assert_eq!(driver.resume_row(), Some(4));
+
+    // Row 4 is past the 4-row landscape height; the flush starts over.
+    driver.set_orientation(Orientation::Landscape).unwrap();
+    assert_eq!(driver.resume_row(), None);
+    driver.interface.clear();
+    driver.flush_resumable(2).unwrap();
+    assert_eq!(windows(&driver), vec![(range(0, 5), range(0, 3))]);
+    assert_eq!(driver.interface.pixel_bytes().len(), 24);
+
+    driver.interface.pass_pixels = 1;
+    driver.interface.fail_pixels = 1;
+    assert!(driver.flush_resumable(2).is_err());
+    assert_eq!(driver.resume_row(), Some(2));
+    driver.set_software_rotation(SoftwareRotation::Rotate90);
+    assert_eq!(driver.resume_row(), None);
+}
+
+#[test]
+fn hard_reset_forgets_cached_panel_state() {
+    let mut driver = driver::<Rgb888, 48>(ColorMode::Rgb888, 4, 4);
+    driver.set_inversion(true).unwrap();
+    driver.set_rad_acl(0x12).unwrap();
+    driver.set_frame_rate_level(0x01).unwrap();
+    driver.set_partial_area(0, 1).unwrap();
+    driver.enable_partial_mode().unwrap();
+    driver.hard_reset().unwrap();
+
+    assert_eq!(driver.reset.resets, 2);
+    assert!(!driver.is_inverted());
+    assert_eq!(driver.rad_acl(), 0x00);
+    assert_eq!(driver.frame_rate_level(), None);
+    assert_eq!(driver.partial_area(), None);
+    assert!(!driver.is_display_on());
+
+    driver.interface.clear();
+    driver.flush().unwrap();
+    assert_eq!(windows(&driver), vec![(range(0, 3), range(0, 3))]);
+}
+
+/// Transfer or delay seen by the expander reset.
+#[derive(Debug, PartialEq, Eq)]
+enum I2cEvent {
+    Write(u8, Vec<u8>),
+    WriteRead(u8, Vec<u8>),
+    DelayMs(u32),
+}
+
+/// Log shared by [`LogI2c`] and [`LogDelay`].
+type I2cLog = alloc::rc::Rc<core::cell::RefCell<Vec<I2cEvent>>>;
+
+/// Delay that logs itself next to the I2C transfers.
+struct LogDelay(I2cLog);
+
+impl DelayNs for LogDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0.borrow_mut().push(I2cEvent::DelayMs(ns / 1_000_000));
+    }
+}
+
+/// I2C bus that logs transfers and answers every register read with `read`.
+struct LogI2c {
+    read: u8,
+    log: I2cLog,
+}
+
+/// Returns a logging bus and delay sharing one log.
+fn log_i2c(read: u8) -> (LogI2c, LogDelay, I2cLog) {
+    let log = I2cLog::default();
+    let i2c = LogI2c {
+        read,
+        log: log.clone(),
+    };
+    (i2c, LogDelay(log.clone()), log)
+}
+
+impl embedded_hal::i2c::ErrorType for LogI2c {
+    type Error = core::convert::Infallible;
+}
+
+impl embedded_hal::i2c::I2c for LogI2c {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        use embedded_hal::i2c::Operation;
+        let mut written = Vec::new();
+        let mut read = false;
+        for op in operations {
+            match op {
+                Operation::Write(bytes) => written.extend_from_slice(bytes),
+                Operation::Read(buf) => {
+                    buf.fill(self.read);
+                    read = true;
+                }
+            }
+        }
+        let event = if read {
+            I2cEvent::WriteRead(address, written)
+        } else {
+            I2cEvent::Write(address, written)
+        };
+        self.log.borrow_mut().push(event);
+        Ok(())
+    }
+}
+
+#[test]
+fn expander_reset_toggles_pin_with_timing() {
+    let (i2c, delay, log) = log_i2c(0xF0);
+    let mut reset = ExpanderResetDriver::pca9535(i2c, delay, 0x20, 9).unwrap();
+    reset.reset().unwrap();
+
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            I2cEvent::WriteRead(0x20, vec![0x03]),
+            I2cEvent::WriteRead(0x20, vec![0x07]),
+            I2cEvent::Write(0x20, vec![0x03, 0xF0]),
+            I2cEvent::Write(0x20, vec![0x07, 0xF0]),
+            I2cEvent::DelayMs(20),
+            I2cEvent::Write(0x20, vec![0x03, 0xF2]),
+            I2cEvent::DelayMs(150),
+        ]
+    );
+}
+
+#[test]
+fn expander_reset_rejects_out_of_range_pin() {
+    let (i2c, delay, _) = log_i2c(0);
+    assert!(ExpanderResetDriver::new(i2c, delay, 0x20, 8).is_err());
+    let (i2c, delay, _) = log_i2c(0);
+    assert!(ExpanderResetDriver::pca9535(i2c, delay, 0x20, 16).is_err());
+    let (i2c, delay, _) = log_i2c(0);
+    assert!(ExpanderResetDriver::new(i2c, delay, 0x20, 7).is_ok());
+}
+
+/// Returns an RGB565 builder for a 4 x 6 panel with a custom init sequence, TEOFF and landscape orientation.
+fn direct_test_builder() -> Rm690b0Builder<MockInterface, MockReset, Rgb565> {
+    Rm690b0Builder::native(MockInterface::new(), MockReset::new())
+        .size(DisplaySize::new(4, 6).with_offset(2, 4))
+        .init_sequence(&[(0xFE, &[0x20]), (0x26, &[0x0A])])
+        .tear_mode(None)
+        .orientation(Orientation::Landscape)
+}
+
+#[test]
+fn direct_driver_initializes_like_framebuffer_driver() {
+    let buffered = direct_test_builder().build_heap::<_, 48>(NoDelay).unwrap();
+    let direct = direct_test_builder().build_direct(NoDelay).unwrap();
+
+    assert_eq!(direct.interface.recorded, buffered.interface.recorded);
+    assert_eq!(direct.size(), Size::new(6, 4));
+}
+
+#[test]
+fn direct_driver_encodes_and_offsets_like_framebuffer_driver() {
+    let mut direct = direct_test_builder().build_direct(NoDelay).unwrap();
+    direct.set_bgr(true);
+    direct.set_rgb565_endianness(Endianness::Little);
+    direct.interface.clear();
+    direct
+        .fill_solid(&Rectangle::new(Point::zero(), Size::new(2, 2)), Rgb565::RED)
+        .unwrap();
+
+    // Landscape swaps the offsets onto the logical axes.
+    assert_eq!(direct.interface.data_of(commands::CASET), [range(4, 5)]);
+    assert_eq!(direct.interface.data_of(commands::RASET), [range(2, 3)]);
+    // Red, swapped to blue, low byte first.
+    assert_eq!(direct.interface.pixel_bytes(), [0x1F, 0x00].repeat(4));
+}
+
+#[cfg(feature = "async")]
+mod asynch {
+    use super::*;
+    use embassy_futures::block_on;
+
+    impl embedded_hal_async::delay::DelayNs for NoDelay {
+        async fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    type AsyncDriver<C> = Rm690b0DriverAsync<MockInterface, MockReset, C>;
+
+    /// Builds a landscape 4 x 6 async driver, with the initialization transfers cleared.
+    fn async_driver() -> AsyncDriver<Rgb565> {
+        let mut driver =
+            block_on(direct_test_builder().build_async_heap::<_, 48>(NoDelay)).unwrap();
+        driver.interface.clear();
+        driver
+    }
+
+    #[test]
+    fn async_driver_initializes_like_blocking_driver() {
+        let blocking = direct_test_builder().build_heap::<_, 48>(NoDelay).unwrap();
+        let driver = block_on(direct_test_builder().build_async_heap::<_, 48>(NoDelay)).unwrap();
+
+        assert_eq!(driver.interface.recorded, blocking.interface.recorded);
+        assert_eq!(driver.size(), Size::new(6, 4));
+    }
+
+    #[test]
+    fn async_orientation_keeps_bgr_color_order() {
+        let mut driver = async_driver();
+        let bgr = PixelFormatOptions {
+            color_order: ColorOrder::Bgr,
+        };
+        block_on(driver.set_pixel_format_option(bgr)).unwrap();
+        block_on(driver.set_orientation(Orientation::Portrait)).unwrap();
+
+        assert_eq!(
+            driver.interface.data_of(commands::MADCTR),
+            [
+                [Orientation::Landscape.madctr() | bgr.madctr_bits()],
+                [Orientation::Portrait.madctr() | bgr.madctr_bits()],
+            ]
+        );
+        assert_eq!(driver.pixel_format_option(), bgr);
+    }
+
+    #[test]
+    fn async_driver_writes_configured_brightness_before_dispon() {
+        let driver = block_on(
+            direct_test_builder()
+                .brightness(0x40)
+                .build_async_heap::<_, 48>(NoDelay),
+        )
+        .unwrap();
+
+        assert_brightness_set_before_dispon(&driver.interface.recorded, 0x40);
+    }
+
+    #[test]
+    fn async_driver_rejects_unsupported_color_mode() {
+        let mut interface = MockInterface::new();
+        interface.supported = &[ColorMode::Rgb888];
+        let result = block_on(AsyncDriver::<Rgb565>::new_heap::<_, 48>(
+            interface,
+            MockReset::new(),
+            ColorMode::Rgb565,
+            DisplaySize::new(4, 6),
+            NoDelay,
+        ));
+
+        assert!(matches!(
+            result,
+            Err(DriverError::InvalidConfiguration(
+                "unsupported color mode for this panel"
+            ))
+        ));
+    }
+
+    #[test]
+    fn async_flush_dirty_sends_widened_region() {
+        let mut driver = async_driver();
+        Pixel(Point::new(3, 1), Rgb565::WHITE)
+            .draw(&mut driver)
+            .unwrap();
+        assert_eq!(
+            driver.dirty_region(),
+            Some(Rectangle::new(Point::new(3, 1), Size::new(1, 1)))
+        );
+        block_on(driver.flush_dirty()).unwrap();
+
+        // Landscape swaps the offsets onto the logical axes.
+        assert_eq!(driver.interface.data_of(commands::CASET), [range(6, 7)]);
+        assert_eq!(driver.interface.data_of(commands::RASET), [range(2, 3)]);
+        assert_eq!(driver.interface.pixel_bytes().len(), 2 * 2 * 2);
+        assert_eq!(driver.dirty_region(), None);
+    }
+
+    #[test]
+    fn async_flush_in_partial_mode_sends_partial_rows() {
+        let mut driver = async_driver();
+        block_on(driver.set_partial_area(2, 3)).unwrap();
+        block_on(driver.enable_partial_mode()).unwrap();
+        driver.interface.clear();
+        block_on(driver.flush()).unwrap();
+
+        // In landscape, panel rows 2..=3 are logical columns 2..=3.
+        assert_eq!(driver.interface.data_of(commands::CASET), [range(6, 7)]);
+        assert_eq!(driver.interface.data_of(commands::RASET), [range(2, 5)]);
+        assert_eq!(driver.interface.pixel_bytes().len(), 2 * 4 * 2);
+    }
+}
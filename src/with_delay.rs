@@ -0,0 +1,182 @@
+//! Driver wrapper that owns its delay provider.
+//!
+//! [`Rm690b0DriverWithDelay`] stores a `DelayNs` implementor next to an
+//! [`Rm690b0Driver`], so methods that need timing can be called without
+//! passing a delay each time. Every other driver method is reachable through
+//! `Deref`/`DerefMut`.
+
+use crate::{ControllerInterface, DriverError, ResetInterface, Rm690b0Driver};
+use embedded_graphics_core::pixelcolor::Rgb888;
+use embedded_graphics_core::prelude::*;
+use embedded_graphics_core::primitives::Rectangle;
+use embedded_hal::delay::DelayNs;
+
+/// An [`Rm690b0Driver`] together with the delay used by its timed methods.
+///
+/// Created with [`Rm690b0Driver::with_delay`]. Drivers that share one delay
+/// can keep using the explicit-delay methods on [`Rm690b0Driver`] instead.
+pub struct Rm690b0DriverWithDelay<IFACE, RST, C, DELAY>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+    DELAY: DelayNs,
+{
+    driver: Rm690b0Driver<IFACE, RST, C>,
+    delay: DELAY,
+}
+
+impl<IFACE, RST, C> Rm690b0Driver<IFACE, RST, C>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+{
+    /// Stores `delay` with the driver, for calling timed methods without a delay argument.
+    pub fn with_delay<DELAY>(self, delay: DELAY) -> Rm690b0DriverWithDelay<IFACE, RST, C, DELAY>
+    where
+        DELAY: DelayNs,
+    {
+        Rm690b0DriverWithDelay {
+            driver: self,
+            delay,
+        }
+    }
+}
+
+impl<IFACE, RST, C, DELAY> Rm690b0DriverWithDelay<IFACE, RST, C, DELAY>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+    DELAY: DelayNs,
+{
+    /// Releases the driver and the delay.
+    pub fn into_parts(self) -> (Rm690b0Driver<IFACE, RST, C>, DELAY) {
+        (self.driver, self.delay)
+    }
+
+    /// Sends the initialization sequence, see [`Rm690b0Driver::initialize_display`].
+    pub fn initialize_display(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.driver.initialize_display(&mut self.delay)
+    }
+
+    /// Sleep Mode In (SLPIN), see [`Rm690b0Driver::sleep_in`].
+    pub fn sleep_in(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.driver.sleep_in(&mut self.delay)
+    }
+
+    /// Sleep Out (SLPOUT), see [`Rm690b0Driver::sleep_out`].
+    pub fn sleep_out(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.driver.sleep_out(&mut self.delay)
+    }
+
+    /// Sleeps and powers down the panel, see [`Rm690b0Driver::shutdown`].
+    pub fn shutdown(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.driver.shutdown(&mut self.delay)
+    }
+
+    /// Wakes the panel from deep standby, see [`Rm690b0Driver::wake_from_deep_standby`].
+    pub fn wake_from_deep_standby(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.driver.wake_from_deep_standby(&mut self.delay)
+    }
+
+    /// Software reset (SWRESET), see [`Rm690b0Driver::soft_reset`].
+    pub fn soft_reset(&mut self) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.driver.soft_reset(&mut self.delay)
+    }
+
+    /// Ramps the brightness to `target`, see [`Rm690b0Driver::fade_brightness`].
+    pub fn fade_brightness(
+        &mut self,
+        target: u8,
+        duration_ms: u32,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.driver
+            .fade_brightness(target, duration_ms, &mut self.delay)
+    }
+
+    /// Flushes with retries on failure, see [`Rm690b0Driver::flush_with_retries`].
+    pub fn flush_with_retries(
+        &mut self,
+        retries: u8,
+        backoff_ms: u32,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.driver
+            .flush_with_retries(retries, &mut self.delay, backoff_ms)
+    }
+
+    /// Briefly shows the inverse image, see [`Rm690b0Driver::anti_burnin_invert`].
+    pub fn anti_burnin_invert(
+        &mut self,
+        duration_ms: u32,
+    ) -> Result<(), DriverError<IFACE::Error, RST::Error>> {
+        self.driver.anti_burnin_invert(duration_ms, &mut self.delay)
+    }
+}
+
+impl<IFACE, RST, C, DELAY> core::ops::Deref for Rm690b0DriverWithDelay<IFACE, RST, C, DELAY>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+    DELAY: DelayNs,
+{
+    type Target = Rm690b0Driver<IFACE, RST, C>;
+    fn deref(&self) -> &Self::Target {
+        &self.driver
+    }
+}
+
+impl<IFACE, RST, C, DELAY> core::ops::DerefMut for Rm690b0DriverWithDelay<IFACE, RST, C, DELAY>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+    DELAY: DelayNs,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.driver
+    }
+}
+
+impl<IFACE, RST, C, DELAY> DrawTarget for Rm690b0DriverWithDelay<IFACE, RST, C, DELAY>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor + Into<Rgb888>,
+    DELAY: DelayNs,
+{
+    type Color = C;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.driver.draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.driver.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.driver.fill_solid(area, color)
+    }
+}
+
+impl<IFACE, RST, C, DELAY> OriginDimensions for Rm690b0DriverWithDelay<IFACE, RST, C, DELAY>
+where
+    IFACE: ControllerInterface,
+    RST: ResetInterface,
+    C: PixelColor,
+    DELAY: DelayNs,
+{
+    fn size(&self) -> Size {
+        self.driver.size()
+    }
+}